@@ -1,9 +1,9 @@
 use std::time::Duration;
 
-use amethyst::ecs::prelude::{Component, DenseVecStorage};
-use rand::Rng;
+use amethyst::ecs::prelude::{Component, DenseVecStorage, Join, WriteStorage};
 
-use crate::systems::spawn::Tetrominos;
+use crate::tetris_core::game::GameOverReason;
+use crate::tetris_core::piece::{ActivePiece, Tetrominos};
 
 /// Internal coordinate component used by the blocks to mark relative positions on the field
 pub struct Block {
@@ -19,160 +19,162 @@ impl Component for Block {
     type Storage = DenseVecStorage<Self>;
 }
 
+/// Moves the falling blocks to the position of the given piece of the game core
+/// All blocks of a piece look the same, so it does not matter which block ends up at which coordinates
+pub fn write_active_piece(piece: &ActivePiece, blocks: &mut WriteStorage<Block>) {
+    let falling = (&mut *blocks).join().filter(|block| block.falling);
+    for (block, (x, y)) in falling.zip(piece.blocks.iter()) {
+        block.x = *x;
+        block.y = *y;
+        block.rotation = piece.rotation;
+    }
+}
 
-/// Resource for keeping track of the position of the current rotation center
-pub struct RotationCenter {
+/// Coordinate component of the ghost blocks, which show where the falling piece would land
+pub struct GhostBlock {
     pub x: i32,
     pub y: i32,
 }
 
-impl Default for RotationCenter {
-    fn default() -> Self {
-        RotationCenter { x: 0, y: 0 }
-    }
-}
-
-/// Keeps track of the time since the last clear occurred so that the spawn system knows when to
-/// spawn the next entity.
-///
-/// Is set by the gravity system and reset by the spawn system.
-pub struct SpawnTimer {
-    pub time_since_clear: Duration,
-    pub spawn_threshold: Duration,
-    pub active: bool,
+impl Component for GhostBlock {
+    type Storage = DenseVecStorage<Self>;
 }
 
-impl SpawnTimer {
-    pub fn add_time(&mut self, time: Duration) {
-        if self.active {
-            self.time_since_clear += time;
-        }
-    }
 
-    pub fn should_spawn(&self) -> bool {
-        self.time_since_clear > self.spawn_threshold && self.active
-    }
+/// Marks the blocks displaying the held piece next to the play field
+#[derive(Clone)]
+pub struct HoldBlock;
 
-    pub fn reset(&mut self) {
-        self.time_since_clear = Duration::new(0,0);
-        self.active = false;
-    }
-
-    pub fn activate(&mut self) {
-        self.active = true;
-    }
+impl Component for HoldBlock {
+    type Storage = DenseVecStorage<Self>;
 }
 
-impl Default for SpawnTimer {
-    fn default() -> Self {
-        // The default SpawnTimer will always trigger a spawn instantly
-        Self {
-            time_since_clear: Duration::from_secs(2),
-            spawn_threshold: Duration::from_secs(1),
-            active: true,
-        }
-    }
-}
+/// Marks the blocks displaying the upcoming pieces next to the play field
+#[derive(Clone)]
+pub struct PreviewBlock;
 
+impl Component for PreviewBlock {
+    type Storage = DenseVecStorage<Self>;
+}
 
 
-/// Keeps track of the time since the last time that gravity was applied
-/// Implements methods for reducing the time between gravity applications
-///
-/// Is both set and reset by the gravity system
-pub struct GravityTimer {
-    pub timer: Duration,
-    pub threshold: Duration,
+/// Marks a line of text in the HUD, which is kept up to date by the HUD system
+/// line: the position of the line in the HUD, counted from the top
+pub struct HudText {
+    pub stat: HudStat,
+    pub line: i32,
 }
 
-impl GravityTimer {
-    pub fn add_time(&mut self, time: Duration) {
-        self.timer += time;
-    }
+impl Component for HudText {
+    type Storage = DenseVecStorage<Self>;
+}
 
-    pub fn should_apply_gravity(&self) -> bool {
-        self.timer > self.threshold
-    }
+/// The values that can be shown in the HUD
+#[derive(Clone, Copy)]
+pub enum HudStat {
+    Score,
+    Level,
+    Lines,
+    Time,
+    PiecesPerSecond,
+}
 
-    pub fn reset(&mut self) {
-        self.timer = Duration::new(0, 0);
-    }
 
-    pub fn set_threshold(&mut self, time: Duration) {
-        self.threshold = time;
-    }
+/// Keeps track of statistics about the current game
+/// seed: the seed the pieces of the game were generated from
+pub struct GameStats {
+    pub lines: u32,
+    pub pieces: u32,
+    pub time: Duration,
+    pub seed: u64,
 }
 
-impl Default for GravityTimer {
+impl Default for GameStats {
     fn default() -> Self {
-        Self {
-            timer: Duration::from_millis(0),
-            threshold: Duration::from_millis(500),
-        }
+        GameStats { lines: 0, pieces: 0, time: Duration::from_secs(0), seed: 0 }
     }
 }
 
 
-/// Keeps track of the current and upcoming pieces and generates the next pieces as they are needed
-pub struct RandomStream {
-    pub next_nums: Vec<u8>,
-    high: u8
+/// Is set as soon as the game has been lost
+///
+/// Set by the game system when the game core ends the game (block out, lock out) and by the goal
+/// system once the goal of the game mode has been reached
+pub struct GameOver {
+    pub reason: Option<GameOverReason>,
 }
 
-impl RandomStream {
-    pub fn advance(&mut self) -> Tetrominos {
-        let current = self.next_nums[0];
-
-        let last = self.next_nums.len() - 1;
-        for i in 0..last {
-            self.next_nums[i] = self.next_nums[i + 1];
-        }
-
-        let mut next_num = rand::thread_rng().gen_range(0, self.high);
-        while next_num == self.next_nums[last - 1] {
-            next_num = rand::thread_rng().gen_range(0, self.high);
-        }
-        self.next_nums[last] = next_num;
-        Tetrominos::num_to_tetromino(current)
+impl GameOver {
+    pub fn is_over(&self) -> bool {
+        self.reason.is_some()
     }
 }
 
-impl Default for RandomStream {
+impl Default for GameOver {
     fn default() -> Self {
-        let low = 0;
-        let high = 7;
-        let length = 4;
-        let mut rng = rand::thread_rng();
-        let mut initial_nums = Vec::new();
-        for i in 0..length {
-            let mut random_num: u8 = rng.gen_range(low, high);
-            if initial_nums.len() > 0 {
-                while initial_nums[i - 1] == random_num {
-                    random_num = rng.gen_range(low, high);
-                }
-            }
-            initial_nums.push(random_num);
-        }
-//        for num in &initial_nums {
-//            println!("{}", num);
-//        }
-        Self { next_nums: initial_nums, high }
+        GameOver { reason: None }
     }
 }
 
 
-
 /// Keeps track of the configuration of the on-screen layout
 /// tile_size: the size in pixels of each block
 /// stack_x/y: position of the play-fields lower left corner relative to the window's lower left corner
+/// hold_x/y: position of the held piece's spawning block relative to the window's lower left corner
+/// preview_x/y: position of the first upcoming piece's spawning block, the following pieces are
+/// placed up to preview_spacing pixels below each other
+/// hud_x/y: position of the center of the HUD's first line, the following lines are placed
+/// hud_spacing pixels below each other
+/// All positions are given in the 640x640 view and scaled with the window
 pub struct LayoutConfig {
     pub tile_size: i32,
     pub stack_x: i32,
     pub stack_y: i32,
+    pub hold_x: i32,
+    pub hold_y: i32,
+    pub preview_x: i32,
+    pub preview_y: i32,
+    pub preview_spacing: i32,
+    pub hud_x: i32,
+    pub hud_y: i32,
+    pub hud_spacing: i32,
+}
+
+impl LayoutConfig {
+    /// Returns how many upcoming pieces fit below each other without overlapping or leaving the view
+    pub fn max_preview(&self) -> usize {
+        (self.preview_height() / (2 * self.tile_size) + 1) as usize
+    }
+
+    /// Returns the distance between the upcoming pieces, which shrinks so that count pieces fit
+    /// into the view
+    pub fn preview_spacing(&self, count: usize) -> i32 {
+        if count < 2 {
+            return self.preview_spacing;
+        }
+        self.preview_spacing.min(self.preview_height() / (count as i32 - 1))
+    }
+
+    // Distance between the spawning blocks of the first and the lowest possible upcoming piece
+    fn preview_height(&self) -> i32 {
+        self.preview_y - self.tile_size / 2
+    }
 }
 
 impl Default for LayoutConfig {
     fn default() -> Self {
-        LayoutConfig {tile_size: 32, stack_x: 0, stack_y: 0}
+        LayoutConfig {
+            tile_size: 32,
+            stack_x: 160,
+            stack_y: 0,
+            hold_x: 64,
+            hold_y: 560,
+            preview_x: 544,
+            preview_y: 560,
+            preview_spacing: 96,
+            hud_x: 80,
+            hud_y: 440,
+            hud_spacing: 36,
+        }
     }
 }
\ No newline at end of file
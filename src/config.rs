@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{ENTRY_DELAY, LINE_CLEAR_DELAY, SOFT_DROP_FACTOR};
+use crate::skin::DEFAULT_SKIN;
+use crate::tetris_core::handling::AutoShift;
+use crate::tetris_core::level::GravityCurve;
+use crate::tetris_core::phase::PhaseTimer;
+use crate::tetris_core::randomizer::{BagRandomizer, RandomStream, Randomizer};
+
+/// Game settings loaded from resources/game_config.ron
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    pub randomizer: RandomizerKind,
+    // Seed for the piece generation, a random seed is used for every game if None
+    pub seed: Option<u64>,
+    // Number of upcoming pieces shown next to the play field
+    pub preview_count: usize,
+    // Whether the landing position of the falling piece is shown
+    pub ghost: bool,
+    pub gravity: GravityKind,
+    // Milliseconds completed rows stay on the board before they are removed
+    pub line_clear_delay: u64,
+    // Milliseconds between locking a piece and the appearance of the next one (ARE)
+    pub entry_delay: u64,
+    // Name of the directory in resources/skins the textures and colours are loaded from,
+    // a changed skin is applied the next time the game is started
+    pub skin: String,
+}
+
+impl GameConfig {
+    /// Creates a new instance of the randomizer selected in the config
+    pub fn create_randomizer(&self, seed: u64) -> Box<dyn Randomizer> {
+        match self.randomizer {
+            RandomizerKind::Stream => Box::new(RandomStream::new(seed)),
+            RandomizerKind::Bag => Box::new(BagRandomizer::new(seed)),
+        }
+    }
+
+    /// Creates the gravity curve selected in the config
+    /// An empty table is replaced by the guideline curve with a warning
+    pub fn create_gravity_curve(&self) -> GravityCurve {
+        match &self.gravity {
+            GravityKind::Guideline => GravityCurve::Guideline,
+            GravityKind::Table(millis) if millis.is_empty() => {
+                log::warn!("The gravity table in game_config.ron is empty, using the guideline curve");
+                GravityCurve::Guideline
+            },
+            GravityKind::Table(millis) => {
+                GravityCurve::Table(millis.iter().map(|ms| Duration::from_millis(*ms)).collect())
+            },
+        }
+    }
+
+    /// Creates the phase timer with the delays set in the config
+    pub fn create_phase_timer(&self) -> PhaseTimer {
+        PhaseTimer::new(Duration::from_millis(self.line_clear_delay), Duration::from_millis(self.entry_delay))
+    }
+
+    /// Returns the seed for the next game
+    pub fn next_seed(&self) -> u64 {
+        self.seed.unwrap_or_else(rand::random)
+    }
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            randomizer: RandomizerKind::Bag,
+            seed: None,
+            preview_count: 5,
+            ghost: true,
+            gravity: GravityKind::Guideline,
+            line_clear_delay: LINE_CLEAR_DELAY.as_millis() as u64,
+            entry_delay: ENTRY_DELAY.as_millis() as u64,
+            skin: DEFAULT_SKIN.to_string(),
+        }
+    }
+}
+
+/// Handling settings loaded from resources/handling_config.ron, all times are in milliseconds
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct HandlingConfig {
+    // Delayed auto shift: how long left or right has to be held before the piece starts moving
+    // on its own
+    pub das: u64,
+    // Auto repeat rate: the time between two automatic moves, 0 moves the piece straight to the wall
+    pub arr: u64,
+    // How long the piece does not repeat its movement after a rotation or spawn, 0 disables it
+    pub das_cut: u64,
+    // How many times faster than regular gravity a piece falls while soft dropping
+    pub soft_drop_factor: u32,
+}
+
+impl HandlingConfig {
+    pub fn das(&self) -> Duration {
+        Duration::from_millis(self.das)
+    }
+
+    pub fn arr(&self) -> Duration {
+        Duration::from_millis(self.arr)
+    }
+
+    pub fn das_cut(&self) -> Duration {
+        Duration::from_millis(self.das_cut)
+    }
+
+    /// Creates the auto shift which repeats left and right with these settings
+    pub fn create_auto_shift(&self) -> AutoShift {
+        AutoShift::new(self.das(), self.arr(), self.das_cut())
+    }
+
+    /// Returns the soft drop factor, a factor of 0 is replaced by 1 with a warning
+    pub fn soft_drop_factor(&self) -> u32 {
+        if self.soft_drop_factor == 0 {
+            log::warn!("The soft drop factor in handling_config.ron must be at least 1, using 1");
+            1
+        } else {
+            self.soft_drop_factor
+        }
+    }
+}
+
+impl Default for HandlingConfig {
+    fn default() -> Self {
+        HandlingConfig { das: 300, arr: 70, das_cut: 0, soft_drop_factor: SOFT_DROP_FACTOR }
+    }
+}
+
+/// Reads the seed passed with "--seed <number>" on the command line
+/// An invalid seed is ignored with a warning
+pub fn seed_from_args() -> Option<u64> {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter()
+        .position(|arg| arg == "--seed")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|seed| match seed.parse() {
+            Ok(seed) => Some(seed),
+            Err(_) => {
+                log::warn!("Ignoring the seed '{}', it has to be a positive integer", seed);
+                None
+            },
+        })
+}
+
+/// The different piece generators available
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RandomizerKind {
+    // Random pieces, never the same piece twice in a row
+    Stream,
+    // Shuffled bags containing every piece once
+    Bag,
+}
+/// The different gravity curves available
+#[derive(Serialize, Deserialize)]
+pub enum GravityKind {
+    // The speed curve of the guideline
+    Guideline,
+    // Milliseconds per row for every level starting at level 1, the last entry is used for all
+    // higher levels
+    Table(Vec<u64>),
+}
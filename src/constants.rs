@@ -1,16 +1,36 @@
 use std::time::Duration;
 
 // The different types of input available to the player
-pub const INPUT_TYPES: [&str; 5] = ["left", "right", "rotate", "descend", "drop"];
+pub const INPUT_TYPES: [&str; 8] = [
+    "left", "right", "rotate", "rotate_ccw", "rotate_180", "descend", "drop", "hold",
+];
 
-// The time after which holding down a key will register as multiple key presses
-pub const KEY_REPEAT_THRESHOLD: Duration = Duration::from_millis(300);
+// How many times faster than regular gravity a piece falls while soft dropping
+pub const SOFT_DROP_FACTOR: u32 = 20;
 
-// How often a key press will be registered while held down
-pub const KEY_REPEAT_TIME: Duration = Duration::from_millis(70);
+// How long a piece may rest on the stack before it is locked
+pub const LOCK_DELAY: Duration = Duration::from_millis(500);
+
+// How often moving or rotating a resting piece may restart the lock delay
+pub const LOCK_RESET_LIMIT: u32 = 15;
+
+// How long completed rows stay on the board before they are removed
+pub const LINE_CLEAR_DELAY: Duration = Duration::from_millis(300);
+
+// How long it takes for the next piece to appear after a piece has been locked (ARE)
+pub const ENTRY_DELAY: Duration = Duration::from_millis(100);
+
+// How many lines have to be cleared to advance to the next level
+pub const LINES_PER_LEVEL: u32 = 10;
+
+// How many lines have to be cleared in a sprint
+pub const SPRINT_LINES: u32 = 40;
+
+// How long an ultra game lasts
+pub const ULTRA_TIME: Duration = Duration::from_secs(120);
 
 // Size of the camera view, should have the same ratio as window size
-pub const VIEW_WIDTH: f32 = 320.;
+pub const VIEW_WIDTH: f32 = 640.;
 pub const VIEW_HEIGHT: f32 = 640.;
 
 // The spawn point on the grid
@@ -19,44 +39,65 @@ pub const SPAWN_POINT: (i32, i32) = (8, 42);
 pub const ARENA_WIDTH: i32 = 20;
 pub const ARENA_HEIGHT: i32 = 46;
 
-// An array of all wall kicks to try and perform
-pub const WALL_KICKS: [[[(i32, i32); 5]; 8]; 2] = [
-    // Wall Kicks for pieces: J, L, S, T, Z
-    [
-        // 0 >> 1
-        [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
-        // 1 >> 0
-        [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
-        // 1 >> 2
-        [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
-        // 2 >> 1
-        [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
-        // 2 >> 3
-        [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
-        // 3 >> 2
-        [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
-        // 0 >> 3
-        [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
-        // 3 >> 0
-        [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
-    ],
-    // Wall Kicks for pieces: I
-    [
-        // 0 >> 1
-        [(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)],
-        // 1 >> 0
-        [(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)],
-        // 1 >> 2
-        [(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)],
-        // 2 >> 1
-        [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)],
-        // 2 >> 3
-        [(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)],
-        // 3 >> 2
-        [(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)],
-        // 0 >> 3
-        [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)],
-        // 3 >> 0
-        [(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)],
-    ]
-];
\ No newline at end of file
+// Height of the visible part of the arena, rows at or above it are only used for spawning
+pub const VISIBLE_HEIGHT: i32 = 40;
+
+// Sprite of the ghost blocks in the sprite sheet
+// NOTE: sprites 0 to 6 are the minos of the pieces I, J, L, O, S, T and Z, sprite 8 is a garbage block
+pub const GHOST_SPRITE: usize = 7;
+
+// The wall kicks of the Super Rotation System, in the order in which they are tried
+// Each table contains one row of kicks per rotation, indexed by rotation::kick_index
+// NOTE: the offsets are given in blocks, with positive y pointing up
+
+// Wall Kicks for pieces: J, L, S, T, Z
+pub const WALL_KICKS_JLSTZ: [[(i32, i32); 5]; 8] = [
+    // 0 >> 1
+    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
+    // 1 >> 0
+    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
+    // 1 >> 2
+    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
+    // 2 >> 1
+    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
+    // 2 >> 3
+    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
+    // 3 >> 2
+    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
+    // 3 >> 0
+    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
+    // 0 >> 3
+    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
+];
+
+// Wall Kicks for pieces: I
+pub const WALL_KICKS_I: [[(i32, i32); 5]; 8] = [
+    // 0 >> 1
+    [(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)],
+    // 1 >> 0
+    [(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)],
+    // 1 >> 2
+    [(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)],
+    // 2 >> 1
+    [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)],
+    // 2 >> 3
+    [(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)],
+    // 3 >> 2
+    [(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)],
+    // 3 >> 0
+    [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)],
+    // 0 >> 3
+    [(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)],
+];
+
+// Wall Kicks for 180 deg rotations of all pieces but O, indexed by the current rotation state
+pub const WALL_KICKS_180: [[(i32, i32); 6]; 4] = [
+    // 0 >> 2
+    [(0, 0), (0, 1), (1, 1), (-1, 1), (1, 0), (-1, 0)],
+    // 1 >> 3
+    [(0, 0), (1, 0), (1, 2), (1, 1), (0, 2), (0, 1)],
+    // 2 >> 0
+    [(0, 0), (0, -1), (-1, -1), (1, -1), (-1, 0), (1, 0)],
+    // 3 >> 1
+    [(0, 0), (-1, 0), (-1, 2), (-1, 1), (0, 2), (0, 1)],
+];
@@ -0,0 +1,146 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use amethyst::config::Config;
+use amethyst::utils::application_dir;
+use serde::{Deserialize, Serialize};
+
+use crate::tetris_core::mode::GameMode;
+
+// How many results are kept for every game mode
+const ENTRIES_PER_MODE: usize = 5;
+
+/// The best results of every game mode, saved to high_scores.ron next to the executable
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct HighScores {
+    pub marathon: Vec<HighScore>,
+    pub sprint: Vec<HighScore>,
+    pub ultra: Vec<HighScore>,
+}
+
+/// A single result, time is given in milliseconds
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HighScore {
+    pub score: u64,
+    pub lines: u32,
+    pub time: u64,
+}
+
+impl HighScores {
+    /// Loads the saved high scores, there are none yet if the file does not exist
+    pub fn load_saved() -> HighScores {
+        let path = path();
+        if path.exists() {
+            HighScores::load(path)
+        } else {
+            HighScores::default()
+        }
+    }
+
+    pub fn save(&self) {
+        if let Err(error) = self.write(path()) {
+            log::warn!("Failed to save the high scores: {}", error);
+        }
+    }
+
+    pub fn entries(&self, mode: GameMode) -> &Vec<HighScore> {
+        match mode {
+            GameMode::Marathon => &self.marathon,
+            GameMode::Sprint => &self.sprint,
+            GameMode::Ultra => &self.ultra,
+        }
+    }
+
+    /// Adds the result if it is good enough to be kept and returns its rank, starting at 0
+    /// Sprints are ranked by time, all other modes by score
+    pub fn add(&mut self, mode: GameMode, entry: HighScore) -> Option<usize> {
+        let entries = match mode {
+            GameMode::Marathon => &mut self.marathon,
+            GameMode::Sprint => &mut self.sprint,
+            GameMode::Ultra => &mut self.ultra,
+        };
+        let rank = entries
+            .iter()
+            .position(|other| match mode {
+                GameMode::Sprint => entry.time < other.time,
+                _ => entry.score > other.score,
+            })
+            .unwrap_or(entries.len());
+        if rank >= ENTRIES_PER_MODE {
+            return None;
+        }
+        entries.insert(rank, entry);
+        entries.truncate(ENTRIES_PER_MODE);
+        Some(rank)
+    }
+}
+
+impl HighScore {
+    pub fn new(score: u64, lines: u32, time: Duration) -> HighScore {
+        HighScore { score, lines, time: time.as_secs() * 1000 + time.subsec_millis() as u64 }
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.time)
+    }
+}
+
+fn path() -> PathBuf {
+    application_dir("high_scores.ron").expect("Failed to find the directory of the executable")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(score: u64, seconds: u64) -> HighScore {
+        HighScore::new(score, 40, Duration::from_secs(seconds))
+    }
+
+    #[test]
+    fn marathon_and_ultra_are_ranked_by_score() {
+        for mode in &[GameMode::Marathon, GameMode::Ultra] {
+            let mut high_scores = HighScores::default();
+            assert_eq!(high_scores.add(*mode, result(500, 60)), Some(0));
+            assert_eq!(high_scores.add(*mode, result(900, 90)), Some(0));
+            assert_eq!(high_scores.add(*mode, result(700, 10)), Some(1));
+            let scores: Vec<u64> = high_scores.entries(*mode).iter().map(|entry| entry.score).collect();
+            assert_eq!(scores, vec![900, 700, 500]);
+        }
+    }
+
+    #[test]
+    fn sprint_is_ranked_by_time() {
+        let mut high_scores = HighScores::default();
+        assert_eq!(high_scores.add(GameMode::Sprint, result(100, 90)), Some(0));
+        assert_eq!(high_scores.add(GameMode::Sprint, result(900, 120)), Some(1));
+        assert_eq!(high_scores.add(GameMode::Sprint, result(0, 60)), Some(0));
+        let times: Vec<u64> = high_scores.entries(GameMode::Sprint).iter().map(|entry| entry.time).collect();
+        assert_eq!(times, vec![60_000, 90_000, 120_000]);
+        assert!(high_scores.entries(GameMode::Marathon).is_empty());
+    }
+
+    #[test]
+    fn only_the_best_results_are_kept() {
+        let mut high_scores = HighScores::default();
+        for score in 1..=ENTRIES_PER_MODE as u64 {
+            high_scores.add(GameMode::Marathon, result(score * 100, 60));
+        }
+        // Equal results rank below the existing ones
+        assert_eq!(high_scores.add(GameMode::Marathon, result(100, 60)), None);
+        assert_eq!(high_scores.add(GameMode::Marathon, result(50, 60)), None);
+        assert_eq!(high_scores.add(GameMode::Marathon, result(250, 60)), Some(3));
+
+        let scores: Vec<u64> = high_scores.entries(GameMode::Marathon).iter().map(|entry| entry.score).collect();
+        assert_eq!(scores, vec![500, 400, 300, 250, 200]);
+    }
+
+    #[test]
+    fn times_are_stored_in_milliseconds() {
+        let entry = HighScore::new(0, 0, Duration::from_millis(83_456));
+        assert_eq!(entry.time, 83_456);
+        assert_eq!(entry.duration(), Duration::from_millis(83_456));
+    }
+}
@@ -1,22 +1,32 @@
 extern crate amethyst;
 extern crate rand;
+extern crate rand_pcg;
+extern crate serde;
 
 use amethyst::assets::ProgressCounter;
+use amethyst::config::Config;
 use amethyst::core::transform::TransformBundle;
 use amethyst::input::InputBundle;
 use amethyst::prelude::*;
-use amethyst::renderer::{DisplayConfig, DrawFlat2D, Pipeline,
+use amethyst::renderer::{ALPHA, ColorMask, DisplayConfig, DrawFlat2D, Pipeline,
                          RenderBundle, Stage};
+use amethyst::ui::{DrawUi, UiBundle};
 use amethyst::utils::application_dir;
 
+use config::{GameConfig, HandlingConfig};
+use high_scores::HighScores;
 use states::load::LoadingState;
 
 use crate::systems::key_update::KeyEvent;
 
 pub mod components;
+pub mod config;
 pub mod systems;
 pub mod states;
 pub mod constants;
+pub mod high_scores;
+pub mod skin;
+pub mod tetris_core;
 
 fn main() -> amethyst::Result<()> {
     amethyst::start_logger(Default::default());
@@ -25,11 +35,22 @@ fn main() -> amethyst::Result<()> {
 
     let binding_path = application_dir("resources/binding_config.ron")?;
 
+    let game_config_path = application_dir("resources/game_config.ron")?;
+    let mut game_config = GameConfig::load(&game_config_path);
+    // A seed given on the command line takes priority over the config file
+    if let Some(seed) = config::seed_from_args() {
+        game_config.seed = Some(seed);
+    }
+
+    let handling_config_path = application_dir("resources/handling_config.ron")?;
+    let handling_config = HandlingConfig::load(&handling_config_path);
+
     let render_pipe = Pipeline::build().
         with_stage(
             Stage::with_backbuffer()
                 .clear_target([0.0, 0.0, 0.0, 1.0], 1.0)
-                .with_pass(DrawFlat2D::new()),
+                .with_pass(DrawFlat2D::new().with_transparency(ColorMask::all(), ALPHA, None))
+                .with_pass(DrawUi::new()),
         );
 
     let input_bundle = InputBundle::<String, String>::new()
@@ -39,10 +60,14 @@ fn main() -> amethyst::Result<()> {
         .with_bundle(RenderBundle::new(render_pipe, Some(display_config))
                 .with_sprite_sheet_processor())?
         .with_bundle(TransformBundle::new())?
-        .with_bundle(input_bundle)?;
+        .with_bundle(input_bundle)?
+        .with_bundle(UiBundle::<String, String>::new())?;
 
-    let mut game =
-        Application::new("./", LoadingState::new(), game_data)?;
+    let mut game = Application::build("./", LoadingState::new())?
+        .with_resource(game_config)
+        .with_resource(handling_config)
+        .with_resource(HighScores::load_saved())
+        .build(game_data)?;
 
     game.run();
 
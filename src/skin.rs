@@ -0,0 +1,81 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Name of the skin which is used if the selected one is missing
+pub const DEFAULT_SKIN: &str = "default";
+
+// The directory which contains one subdirectory per skin
+const SKIN_DIR: &str = "resources/skins";
+
+// The files every skin has to provide
+const SKIN_FILES: [&str; 5] = [
+    "spritesheet.png", "spritesheet.ron", "background.png", "background.ron", "palette.ron",
+];
+
+/// The skin the game has been started with
+/// The sprite sheet of the blocks is added as a resource of its own
+pub struct Skin {
+    pub name: String,
+    pub palette: Palette,
+}
+
+/// Colours of a skin, loaded from resources/skins/<name>/palette.ron
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Palette {
+    // Colour of all text
+    pub text: [f32; 4],
+    // Colour of the highlighted option of a menu
+    pub selected: [f32; 4],
+    // Tint of the ghost blocks
+    pub ghost: [f32; 4],
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            text: [1., 1., 1., 1.],
+            selected: [1., 0.8, 0.2, 1.],
+            ghost: [1., 1., 1., 0.8],
+        }
+    }
+}
+
+/// Returns the path of one of the files of a skin
+pub fn skin_file(name: &str, file: &str) -> String {
+    format!("{}/{}/{}", SKIN_DIR, name, file)
+}
+
+/// Returns whether all files of the skin exist
+pub fn is_complete(name: &str) -> bool {
+    SKIN_FILES.iter().all(|file| Path::new(&skin_file(name, file)).is_file())
+}
+
+/// Returns the given skin if it is complete, otherwise the default skin
+pub fn resolve(name: &str) -> String {
+    if is_complete(name) {
+        name.to_string()
+    } else {
+        log::warn!("Skin '{}' is missing or incomplete, using the '{}' skin instead", name, DEFAULT_SKIN);
+        DEFAULT_SKIN.to_string()
+    }
+}
+
+/// Returns the names of all complete skins in alphabetical order
+pub fn available_skins() -> Vec<String> {
+    let mut skins: Vec<String> = match fs::read_dir(SKIN_DIR) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| is_complete(name))
+            .collect(),
+        Err(error) => {
+            log::warn!("Failed to read the skin directory: {}", error);
+            Vec::new()
+        },
+    };
+    skins.sort();
+    skins
+}
@@ -0,0 +1,96 @@
+use amethyst::ecs::Entity;
+use amethyst::input::InputEvent;
+use amethyst::prelude::*;
+use amethyst::shrev::{EventChannel, ReaderId};
+use amethyst::ui::{Anchor, FontHandle};
+
+use crate::components::{GameOver, GameStats};
+use crate::high_scores::{HighScore, HighScores};
+use crate::states::gameplay::{GameplayState, remove_blocks};
+use crate::states::menu::MenuState;
+use crate::states::ui::{create_text, format_time, read_actions};
+use crate::tetris_core::game::{Game, GameOverReason};
+use crate::tetris_core::mode::GameMode;
+
+/// Shown once the game has ended, displays the final statistics of the game and records them in
+/// the high scores
+/// The game can either be restarted ("confirm") or the main menu opened ("back")
+pub struct GameOverState {
+    input_reader: Option<ReaderId<InputEvent<String>>>,
+    ui_entities: Vec<Entity>,
+}
+
+impl GameOverState {
+    pub fn new() -> GameOverState {
+        GameOverState {input_reader: None, ui_entities: Vec::new()}
+    }
+}
+
+impl SimpleState for GameOverState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+        self.input_reader = Some(world.write_resource::<EventChannel<InputEvent<String>>>().register_reader());
+
+        let lines = {
+            let stats = world.read_resource::<GameStats>();
+            let game = world.read_resource::<Game>();
+            let mode = *world.read_resource::<GameMode>();
+            let game_over_reason = world.read_resource::<GameOver>().reason;
+            let (title, mut reason) = match game_over_reason {
+                Some(GameOverReason::BlockOut) => ("GAME OVER", "Block out".to_string()),
+                Some(GameOverReason::LockOut) => ("GAME OVER", "Lock out".to_string()),
+                Some(GameOverReason::Finished) => ("FINISHED", format!("{} complete", mode.name())),
+                None => ("GAME OVER", String::new()),
+            };
+
+            // A sprint only counts if all of its lines have been cleared
+            if mode != GameMode::Sprint || game_over_reason == Some(GameOverReason::Finished) {
+                let mut high_scores = world.write_resource::<HighScores>();
+                let entry = HighScore::new(game.score.points, stats.lines, stats.time);
+                if let Some(rank) = high_scores.add(mode, entry) {
+                    high_scores.save();
+                    reason = format!("{}  New high score #{}", reason, rank + 1);
+                }
+            }
+
+            vec![
+                (title.to_string(), 40.),
+                (reason, 20.),
+                (format!("Score: {}", game.score.points), 20.),
+                (format!("Level: {}", game.level.level), 20.),
+                (format!("Lines: {}", stats.lines), 20.),
+                (format!("Pieces: {}", stats.pieces), 20.),
+                (format!("Time: {}", format_time(stats.time)), 20.),
+                (format!("Seed: {}", stats.seed), 16.),
+                ("Enter: restart  Esc: menu".to_string(), 16.),
+            ]
+        };
+
+        let font = world.read_resource::<FontHandle>().clone();
+        for (i, (text, font_size)) in lines.into_iter().enumerate() {
+            let y = 150. - 50. * i as f32;
+            let entity = create_text(world, &font, &format!("game_over_{}", i), text, Anchor::Middle, (0., y), font_size);
+            self.ui_entities.push(entity);
+        }
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        data.world.delete_entities(&self.ui_entities).expect("Failed to remove the game over text");
+        self.ui_entities.clear();
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        for action in read_actions(data.world, self.input_reader.as_mut().unwrap()) {
+            match action.as_str() {
+                "confirm" => return Trans::Switch(Box::new(GameplayState::new())),
+                "back" => {
+                    // The last board is shown until the game over screen is left for the menu
+                    remove_blocks(data.world);
+                    return Trans::Switch(Box::new(MenuState::new()));
+                },
+                _ => (),
+            }
+        }
+        Trans::None
+    }
+}
@@ -1,21 +1,64 @@
 use amethyst::{GameData, SimpleState, SimpleTrans, State, StateData, Trans};
-use amethyst::ecs::prelude::{Dispatcher, DispatcherBuilder};
+use amethyst::ecs::prelude::{Dispatcher, DispatcherBuilder, Entity, Join};
+use amethyst::input::InputEvent;
+use amethyst::prelude::*;
+use amethyst::shrev::{EventChannel, ReaderId};
+use amethyst::ui::{Anchor, FontHandle};
 
-use crate::systems::gravity::GravitySystem;
+use crate::components::{Block, GameOver, GameStats, GhostBlock, HoldBlock, HudStat, HudText, PreviewBlock};
+use crate::config::{GameConfig, HandlingConfig};
+use crate::states::game_over::GameOverState;
+use crate::states::menu::MenuState;
+use crate::states::pause::{PauseChoice, PauseState};
+use crate::states::ui::create_text;
+use crate::systems::blocks::BlockSystem;
+use crate::systems::game::GameSystem;
+use crate::systems::ghost::GhostSystem;
+use crate::systems::goal::GoalSystem;
+use crate::systems::hold_display::HoldDisplaySystem;
+use crate::systems::hud::HudSystem;
 use crate::systems::key_update::KeyUpdateSystem;
 use crate::systems::pos_update::PositionUpdateSystem;
-use crate::systems::rotation::RotationSystem;
-use crate::systems::spawn::SpawnSystem;
-use crate::systems::timing::TimingSystem;
-use crate::systems::translation::TranslationSystem;
+use crate::systems::preview::PreviewDisplaySystem;
+use crate::systems::stats::StatsSystem;
+use crate::tetris_core::game::Game;
 
+/// Runs the game, "pause" pushes the pause state on top of it
 pub struct GameplayState<'a, 'b> {
-    dispatcher: Option<Dispatcher<'a, 'b>>
+    dispatcher: Option<Dispatcher<'a, 'b>>,
+    input_reader: Option<ReaderId<InputEvent<String>>>,
+    ui_entities: Vec<Entity>,
+    // Whether quit was chosen in the pause menu, the game returns to the main menu on the next update
+    quit: bool,
 }
 
 impl<'a, 'b> GameplayState<'a, 'b> {
     pub fn new() -> GameplayState<'a, 'b> {
-        GameplayState {dispatcher: None}
+        GameplayState {dispatcher: None, input_reader: None, ui_entities: Vec::new(), quit: false}
+    }
+
+    /// Starts a new game, creates the HUD and shows the seed so that the game can be reproduced
+    fn start_game(&mut self, world: &mut World) {
+        world.delete_entities(&self.ui_entities).expect("Failed to remove the gameplay text");
+        self.ui_entities.clear();
+
+        reset_game(world);
+
+        let text = format!("Seed: {}", world.read_resource::<GameStats>().seed);
+        let font = world.read_resource::<FontHandle>().clone();
+        let seed_text = create_text(world, &font, "seed", text, Anchor::TopMiddle, (0., -12.), 12.);
+        self.ui_entities.push(seed_text);
+
+        // The HUD system fills in the text and moves the lines to their place in the layout
+        let stats = [HudStat::Score, HudStat::Level, HudStat::Lines, HudStat::Time, HudStat::PiecesPerSecond];
+        for (line, stat) in stats.iter().enumerate() {
+            let id = format!("hud_{}", line);
+            let entity = create_text(world, &font, &id, String::new(), Anchor::BottomLeft, (0., 0.), 16.);
+            world.write_storage::<HudText>()
+                .insert(entity, HudText { stat: *stat, line: line as i32 })
+                .expect("Failed to add a line to the HUD");
+            self.ui_entities.push(entity);
+        }
     }
 }
 
@@ -24,25 +67,110 @@ impl<'a, 'b> SimpleState for GameplayState<'a, 'b> {
     fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
 
         let mut dispatcher_builder = DispatcherBuilder::new();
-        dispatcher_builder.add(TimingSystem, "timing", &[]);
-        dispatcher_builder.add(GravitySystem, "gravity", &["timing"]);
-        dispatcher_builder.add(SpawnSystem, "spawn", &["timing"]);
-        dispatcher_builder.add(PositionUpdateSystem, "render_update", &[]);
         dispatcher_builder.add(KeyUpdateSystem::new(), "key_update", &[]);
-        dispatcher_builder.add(TranslationSystem::new(), "translation", &["key_update"]);
-        dispatcher_builder.add(RotationSystem::new(), "rotation", &["key_update"]);
+        dispatcher_builder.add(GameSystem::new(), "game", &["key_update"]);
+        dispatcher_builder.add(BlockSystem::new(), "blocks", &["game"]);
+        dispatcher_builder.add(StatsSystem::new(), "stats", &["game"]);
+        dispatcher_builder.add(GoalSystem, "goal", &["stats"]);
+        dispatcher_builder.add(HudSystem, "hud", &["stats"]);
+        dispatcher_builder.add(GhostSystem::new(), "ghost", &["game"]);
+        dispatcher_builder.add(PositionUpdateSystem, "render_update", &["blocks", "ghost"]);
+        dispatcher_builder.add(HoldDisplaySystem::new(), "hold_display", &["game"]);
+        dispatcher_builder.add(PreviewDisplaySystem::new(), "preview_display", &["game"]);
 
         let mut dispatcher = dispatcher_builder.build();
         dispatcher.setup(&mut data.world.res);
         self.dispatcher = Some(dispatcher);
+
+        self.input_reader = Some(data.world.write_resource::<EventChannel<InputEvent<String>>>().register_reader());
+        self.start_game(data.world);
+    }
+
+    fn on_resume(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        // Skip everything that was pressed while the game was paused
+        data.world.read_resource::<EventChannel<InputEvent<String>>>()
+            .read(self.input_reader.as_mut().unwrap())
+            .for_each(|_| ());
+
+        let choice = *data.world.read_resource::<PauseChoice>();
+        match choice {
+            PauseChoice::Resume => (),
+            PauseChoice::Restart => self.start_game(data.world),
+            PauseChoice::Quit => self.quit = true,
+        }
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        data.world.delete_entities(&self.ui_entities).expect("Failed to remove the gameplay text");
+        self.ui_entities.clear();
+        // The game over state keeps showing the board and removes it once it is left
+        let over = data.world.read_resource::<GameOver>().is_over();
+        if !over {
+            remove_blocks(data.world);
+        }
     }
 
     fn update(&mut self, data: &mut StateData<GameData>) -> SimpleTrans {
+        if self.quit {
+            return Trans::Switch(Box::new(MenuState::new()));
+        }
+
+        let paused = data.world.read_resource::<EventChannel<InputEvent<String>>>()
+            .read(self.input_reader.as_mut().unwrap())
+            .any(|event| match event {
+                InputEvent::ActionPressed(action) => action == "pause",
+                _ => false,
+            });
+        if paused {
+            return Trans::Push(Box::new(PauseState::new()));
+        }
+
         if let Some(dispatcher) = self.dispatcher.as_mut() {
             dispatcher.dispatch(&data.world.res);
         }
+        if data.world.read_resource::<GameOver>().is_over() {
+            return Trans::Switch(Box::new(GameOverState::new()));
+        }
         Trans::None
     }
 
 
+}
+
+/// Removes the blocks of the play field, the ghost, hold and preview from the world
+pub fn remove_blocks(world: &mut World) {
+    let blocks: Vec<Entity> = {
+        let entities = world.entities();
+        let blocks = world.read_storage::<Block>();
+        let hold_blocks = world.read_storage::<HoldBlock>();
+        let preview_blocks = world.read_storage::<PreviewBlock>();
+        let ghost_blocks = world.read_storage::<GhostBlock>();
+        (&*entities, &blocks).join().map(|(entity, _)| entity)
+            .chain((&*entities, &ghost_blocks).join().map(|(entity, _)| entity))
+            .chain((&*entities, &hold_blocks).join().map(|(entity, _)| entity))
+            .chain((&*entities, &preview_blocks).join().map(|(entity, _)| entity))
+            .collect()
+    };
+    world.delete_entities(&blocks).expect("Failed to remove the blocks");
+}
+
+/// Removes all blocks left over from a previous game and starts a new game
+fn reset_game(world: &mut World) {
+    remove_blocks(world);
+    let (seed, mut game) = {
+        let config = world.read_resource::<GameConfig>();
+        let seed = config.next_seed();
+        let mut game = Game::new(config.create_randomizer(seed), config.create_gravity_curve());
+        game.phase_timer = config.create_phase_timer();
+        (seed, game)
+    };
+    let auto_shift = {
+        let handling = world.read_resource::<HandlingConfig>();
+        game.gravity_timer.soft_drop_factor = handling.soft_drop_factor();
+        handling.create_auto_shift()
+    };
+    world.add_resource(auto_shift);
+    world.add_resource(game);
+    world.add_resource(GameStats { seed, ..GameStats::default() });
+    world.add_resource(GameOver::default());
 }
\ No newline at end of file
@@ -0,0 +1,83 @@
+use amethyst::ecs::Entity;
+use amethyst::input::InputEvent;
+use amethyst::prelude::*;
+use amethyst::shrev::{EventChannel, ReaderId};
+use amethyst::ui::{Anchor, FontHandle};
+
+use crate::high_scores::HighScores;
+use crate::states::ui::{create_text, format_time, read_actions};
+use crate::tetris_core::mode::GameMode;
+
+const MODES: [GameMode; 3] = [GameMode::Marathon, GameMode::Sprint, GameMode::Ultra];
+
+/// Pushed on top of the main menu, lists the best results of one game mode at a time
+/// "left" and "right" switch between the modes, "back" and "confirm" return to the menu
+pub struct HighScoresState {
+    input_reader: Option<ReaderId<InputEvent<String>>>,
+    ui_entities: Vec<Entity>,
+    mode: usize,
+}
+
+impl HighScoresState {
+    pub fn new() -> HighScoresState {
+        HighScoresState {input_reader: None, ui_entities: Vec::new(), mode: 0}
+    }
+
+    fn show(&mut self, world: &mut World) {
+        world.delete_entities(&self.ui_entities).expect("Failed to remove the high scores");
+        self.ui_entities.clear();
+
+        let mode = MODES[self.mode];
+        let mut lines = vec![
+            ("HIGH SCORES".to_string(), 40.),
+            (format!("< {} >", mode.name()), 24.),
+        ];
+        {
+            let high_scores = world.read_resource::<HighScores>();
+            let entries = high_scores.entries(mode);
+            if entries.is_empty() {
+                lines.push(("No results yet".to_string(), 20.));
+            }
+            for (rank, entry) in entries.iter().enumerate() {
+                let text = match mode {
+                    GameMode::Sprint => format!("{}. {}", rank + 1, format_time(entry.duration())),
+                    _ => format!("{}. {}  ({} lines)", rank + 1, entry.score, entry.lines),
+                };
+                lines.push((text, 20.));
+            }
+        }
+        lines.push(("Esc: back".to_string(), 16.));
+
+        let font = world.read_resource::<FontHandle>().clone();
+        for (i, (text, font_size)) in lines.into_iter().enumerate() {
+            let y = 200. - 50. * i as f32;
+            let entity = create_text(world, &font, &format!("high_scores_{}", i), text, Anchor::Middle, (0., y), font_size);
+            self.ui_entities.push(entity);
+        }
+    }
+}
+
+impl SimpleState for HighScoresState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        self.input_reader = Some(data.world.write_resource::<EventChannel<InputEvent<String>>>().register_reader());
+        self.show(data.world);
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        data.world.delete_entities(&self.ui_entities).expect("Failed to remove the high scores");
+        self.ui_entities.clear();
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        for action in read_actions(data.world, self.input_reader.as_mut().unwrap()) {
+            match action.as_str() {
+                "back" | "confirm" => return Trans::Pop,
+                "left" => self.mode = (self.mode + MODES.len() - 1) % MODES.len(),
+                "right" => self.mode = (self.mode + 1) % MODES.len(),
+                _ => continue,
+            }
+            self.show(data.world);
+        }
+        Trans::None
+    }
+}
@@ -1,4 +1,5 @@
 use amethyst::assets::{AssetStorage, Loader, ProgressCounter};
+use amethyst::config::Config;
 use amethyst::core::transform::Transform;
 use amethyst::ecs::prelude::{Component, DenseVecStorage};
 use amethyst::prelude::*;
@@ -7,10 +8,14 @@ use amethyst::renderer::{
     SpriteSheetFormat, SpriteSheetHandle, Texture, TextureMetadata,
 };
 use amethyst::shrev::EventChannel;
+use amethyst::ui::{FontAsset, TtfFormat};
 
+use crate::config::GameConfig;
 use crate::constants::{VIEW_HEIGHT, VIEW_WIDTH};
-use crate::states::gameplay::GameplayState;
+use crate::skin::{self, Palette, Skin};
+use crate::states::menu::MenuState;
 use crate::systems::key_update::KeyEvent;
+use crate::tetris_core::game::GameEvent;
 
 pub struct LoadingState {
     pub progress_counter: ProgressCounter,
@@ -26,38 +31,39 @@ impl SimpleState for LoadingState {
     fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
         init_camera(data.world);
 
-        let texture_handle = {
-            let loader = data.world.read_resource::<Loader>();
-            loader.load(
-                "resources/texture/tetris_spritesheet.png",
-                PngFormat,
-                TextureMetadata::srgb_scale(),
-                &mut self.progress_counter,
-                &data.world.read_resource::<AssetStorage<Texture>>(),
-            )
-        };
+        // A skin which doesn't exist is replaced by the default skin instead of failing to load
+        let skin_name = skin::resolve(&data.world.read_resource::<GameConfig>().skin);
+        let sprite_sheet_handle = load_sprite_sheet(
+            data.world, &skin_name, "spritesheet", &mut self.progress_counter);
+        let background_handle = load_sprite_sheet(
+            data.world, &skin_name, "background", &mut self.progress_counter);
+        let palette = Palette::load(skin::skin_file(&skin_name, "palette.ron"));
+        init_background(data.world, background_handle);
 
-        let sprite_sheet_handle = {
+        let font_handle = {
             let loader = data.world.read_resource::<Loader>();
             loader.load(
-                "resources/texture/tetris_spritesheet.ron", // Here we load the associated ron file
-                SpriteSheetFormat,
-                texture_handle, // We pass it the texture we want it to use
+                "resources/font/DejaVuSansMono.ttf",
+                TtfFormat,
+                Default::default(),
                 &mut self.progress_counter,
-                &data.world.read_resource::<AssetStorage<SpriteSheet>>(),
+                &data.world.read_resource::<AssetStorage<FontAsset>>(),
             )
         };
 
-        // add SpriteSheetHandle as resource
-        data.world.add_resource(sprite_sheet_handle.clone());
+        // add SpriteSheetHandle, FontHandle and the skin as resources
+        data.world.add_resource(sprite_sheet_handle);
+        data.world.add_resource(font_handle);
+        data.world.add_resource(Skin {name: skin_name, palette});
 
         // initialize event channels
         data.world.add_resource(EventChannel::<KeyEvent>::new());
+        data.world.add_resource(EventChannel::<GameEvent>::new());
     }
 
     fn update(&mut self, _data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
         if self.progress_counter.is_complete() {
-            Trans::Switch(Box::new(GameplayState::new()))
+            Trans::Switch(Box::new(MenuState::new()))
         } else {
             Trans::None
         }
@@ -65,6 +71,30 @@ impl SimpleState for LoadingState {
 }
 
 
+/// Loads the texture and sprite sheet file of a skin with the given name (without the extension)
+fn load_sprite_sheet(
+    world: &World,
+    skin_name: &str,
+    name: &str,
+    progress_counter: &mut ProgressCounter,
+) -> SpriteSheetHandle {
+    let loader = world.read_resource::<Loader>();
+    let texture_handle = loader.load(
+        skin::skin_file(skin_name, &format!("{}.png", name)),
+        PngFormat,
+        TextureMetadata::srgb_scale(),
+        &mut *progress_counter,
+        &world.read_resource::<AssetStorage<Texture>>(),
+    );
+    loader.load(
+        skin::skin_file(skin_name, &format!("{}.ron", name)),
+        SpriteSheetFormat,
+        texture_handle, // The sprites of the sheet are cut from this texture
+        progress_counter,
+        &world.read_resource::<AssetStorage<SpriteSheet>>(),
+    )
+}
+
 fn init_camera(world: &mut World) {
     let mut transform = Transform::default();
     transform.set_translation_z(1.0);
@@ -74,4 +104,15 @@ fn init_camera(world: &mut World) {
         0.0, VIEW_WIDTH, 0.0, VIEW_HEIGHT)))
         .with(transform)
         .build();
+}
+
+/// Creates the background of the skin, which covers the whole view behind everything else
+fn init_background(world: &mut World, sprite_sheet: SpriteSheetHandle) {
+    let mut transform = Transform::default();
+    transform.set_translation_xyz(VIEW_WIDTH / 2., VIEW_HEIGHT / 2., -1.);
+    world
+        .create_entity()
+        .with(SpriteRender {sprite_sheet, sprite_number: 0})
+        .with(transform)
+        .build();
 }
\ No newline at end of file
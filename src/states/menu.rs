@@ -1,10 +1,94 @@
-use amethyst::{SimpleState, StateData, GameData};
+use amethyst::ecs::Entity;
+use amethyst::input::InputEvent;
+use amethyst::prelude::*;
+use amethyst::shrev::{EventChannel, ReaderId};
+use amethyst::ui::{Anchor, FontHandle};
 
+use crate::states::gameplay::GameplayState;
+use crate::states::high_scores::HighScoresState;
+use crate::states::settings::SettingsState;
+use crate::states::ui::{create_text, MenuList, read_actions};
+use crate::tetris_core::mode::GameMode;
+
+const MODES: [GameMode; 3] = [GameMode::Marathon, GameMode::Sprint, GameMode::Ultra];
+
+/// Shown after loading, starts a game in the chosen mode or opens the settings and high scores
+/// on top of itself
 pub struct MenuState {
+    input_reader: Option<ReaderId<InputEvent<String>>>,
+    ui_entities: Vec<Entity>,
+    menu: Option<MenuList>,
+    // Selection to restore when returning from the settings or high scores
+    selected: usize,
+}
+
+impl MenuState {
+    pub fn new() -> MenuState {
+        MenuState {input_reader: None, ui_entities: Vec::new(), menu: None, selected: 0}
+    }
+
+    fn show(&mut self, world: &mut World) {
+        let font = world.read_resource::<FontHandle>().clone();
+        let title = create_text(world, &font, "menu_title", "TETRIS".to_string(), Anchor::Middle, (0., 160.), 48.);
+        self.ui_entities.push(title);
+
+        let options = ["Marathon", "Sprint", "Ultra", "Settings", "High Scores", "Quit"];
+        let mut menu = MenuList::create(world, &font, "menu", &options, 80.);
+        menu.select(world, self.selected);
+        self.menu = Some(menu);
+    }
+
+    fn hide(&mut self, world: &mut World) {
+        if let Some(menu) = self.menu.take() {
+            self.selected = menu.selected;
+            self.ui_entities.extend(menu.entities);
+        }
+        world.delete_entities(&self.ui_entities).expect("Failed to remove the menu");
+        self.ui_entities.clear();
+    }
 }
 
 impl SimpleState for MenuState {
-    fn on_start(&mut self, _data: StateData<GameData>) {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        self.input_reader = Some(data.world.write_resource::<EventChannel<InputEvent<String>>>().register_reader());
+        self.show(data.world);
+    }
+
+    fn on_pause(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        self.hide(data.world);
+    }
+
+    fn on_resume(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        // Skip everything that was pressed in the settings or high scores
+        data.world.read_resource::<EventChannel<InputEvent<String>>>()
+            .read(self.input_reader.as_mut().unwrap())
+            .for_each(|_| ());
+        self.show(data.world);
+    }
 
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        self.hide(data.world);
     }
-}
\ No newline at end of file
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        let actions = read_actions(data.world, self.input_reader.as_mut().unwrap());
+
+        for action in actions {
+            if action == "back" {
+                return Trans::Quit;
+            }
+            let menu = self.menu.as_mut().unwrap();
+            match menu.handle_action(data.world, &action) {
+                Some(i) if i < MODES.len() => {
+                    data.world.add_resource(MODES[i]);
+                    return Trans::Switch(Box::new(GameplayState::new()));
+                },
+                Some(3) => return Trans::Push(Box::new(SettingsState::new())),
+                Some(4) => return Trans::Push(Box::new(HighScoresState::new())),
+                Some(_) => return Trans::Quit,
+                None => (),
+            }
+        }
+        Trans::None
+    }
+}
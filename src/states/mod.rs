@@ -1,4 +1,8 @@
 pub mod gameplay;
+pub mod game_over;
+pub mod high_scores;
 pub mod pause;
 pub mod menu;
-pub mod load;
\ No newline at end of file
+pub mod load;
+pub mod settings;
+pub mod ui;
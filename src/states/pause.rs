@@ -0,0 +1,80 @@
+use amethyst::ecs::Entity;
+use amethyst::input::InputEvent;
+use amethyst::prelude::*;
+use amethyst::shrev::{EventChannel, ReaderId};
+use amethyst::ui::{Anchor, FontHandle};
+
+use crate::states::ui::{create_text, MenuList, read_actions};
+
+/// Pushed on top of the gameplay state, whose systems do not run while it is paused
+/// Offers to resume, restart or quit to the main menu, "pause" and "back" resume the game as well
+pub struct PauseState {
+    input_reader: Option<ReaderId<InputEvent<String>>>,
+    ui_entities: Vec<Entity>,
+    menu: Option<MenuList>,
+}
+
+impl PauseState {
+    pub fn new() -> PauseState {
+        PauseState {input_reader: None, ui_entities: Vec::new(), menu: None}
+    }
+}
+
+impl SimpleState for PauseState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+        self.input_reader = Some(world.write_resource::<EventChannel<InputEvent<String>>>().register_reader());
+        world.add_resource(PauseChoice::Resume);
+
+        let font = world.read_resource::<FontHandle>().clone();
+        let title = create_text(world, &font, "pause_title", "PAUSED".to_string(), Anchor::Middle, (0., 100.), 40.);
+        self.ui_entities.push(title);
+        self.menu = Some(MenuList::create(world, &font, "pause_menu", &["Resume", "Restart", "Quit"], 20.));
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        if let Some(menu) = self.menu.take() {
+            self.ui_entities.extend(menu.entities);
+        }
+        data.world.delete_entities(&self.ui_entities).expect("Failed to remove the pause overlay");
+        self.ui_entities.clear();
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        let actions = read_actions(data.world, self.input_reader.as_mut().unwrap());
+
+        for action in actions {
+            if action == "pause" || action == "back" {
+                return Trans::Pop;
+            }
+            let menu = self.menu.as_mut().unwrap();
+            match menu.handle_action(data.world, &action) {
+                Some(0) => return Trans::Pop,
+                Some(1) => {
+                    data.world.add_resource(PauseChoice::Restart);
+                    return Trans::Pop;
+                },
+                Some(_) => {
+                    data.world.add_resource(PauseChoice::Quit);
+                    return Trans::Pop;
+                },
+                None => (),
+            }
+        }
+        Trans::None
+    }
+}
+
+/// What the player chose in the pause menu, read by the gameplay state once it is resumed
+#[derive(Clone, Copy, PartialEq)]
+pub enum PauseChoice {
+    Resume,
+    Restart,
+    Quit,
+}
+
+impl Default for PauseChoice {
+    fn default() -> Self {
+        PauseChoice::Resume
+    }
+}
@@ -0,0 +1,168 @@
+use amethyst::config::Config;
+use amethyst::ecs::Entity;
+use amethyst::input::InputEvent;
+use amethyst::prelude::*;
+use amethyst::shrev::{EventChannel, ReaderId};
+use amethyst::ui::{Anchor, FontHandle};
+use amethyst::utils::application_dir;
+
+use crate::components::LayoutConfig;
+use crate::config::{GameConfig, HandlingConfig, RandomizerKind};
+use crate::skin::{self, Skin};
+use crate::states::ui::{create_text, MenuList, read_actions};
+
+// The options in the order they are listed
+const GHOST: usize = 0;
+const PREVIEW: usize = 1;
+const RANDOMIZER: usize = 2;
+const SKIN: usize = 3;
+const DAS: usize = 4;
+const ARR: usize = 5;
+const DAS_CUT: usize = 6;
+const BACK: usize = 7;
+
+/// Pushed on top of the main menu, changes the game and handling config
+/// The values are changed with "left" and "right" and saved to the config files on leaving
+pub struct SettingsState {
+    input_reader: Option<ReaderId<InputEvent<String>>>,
+    ui_entities: Vec<Entity>,
+    menu: Option<MenuList>,
+}
+
+impl SettingsState {
+    pub fn new() -> SettingsState {
+        SettingsState {input_reader: None, ui_entities: Vec::new(), menu: None}
+    }
+
+    /// Changes the value of the option by one step in the given direction
+    fn change(&self, world: &mut World, option: usize, step: i64) {
+        {
+            let mut game_config = world.write_resource::<GameConfig>();
+            let mut handling = world.write_resource::<HandlingConfig>();
+            match option {
+                GHOST => game_config.ghost = !game_config.ghost,
+                PREVIEW => {
+                    let max_preview = LayoutConfig::default().max_preview() as i64;
+                    let count = game_config.preview_count as i64 + step;
+                    game_config.preview_count = count.max(0).min(max_preview) as usize;
+                },
+                RANDOMIZER => game_config.randomizer = match game_config.randomizer {
+                    RandomizerKind::Stream => RandomizerKind::Bag,
+                    RandomizerKind::Bag => RandomizerKind::Stream,
+                },
+                SKIN => {
+                    let skins = skin::available_skins();
+                    if !skins.is_empty() {
+                        let current = skins.iter().position(|name| *name == game_config.skin).unwrap_or(0) as i64;
+                        let count = skins.len() as i64;
+                        let index = ((current + step) % count + count) % count;
+                        game_config.skin = skins[index as usize].clone();
+                    }
+                },
+                DAS => handling.das = step_millis(handling.das, step),
+                ARR => handling.arr = step_millis(handling.arr, step),
+                DAS_CUT => handling.das_cut = step_millis(handling.das_cut, step),
+                _ => (),
+            }
+        }
+        self.refresh(world);
+    }
+
+    fn refresh(&self, world: &mut World) {
+        let texts = {
+            let game_config = world.read_resource::<GameConfig>();
+            let handling = world.read_resource::<HandlingConfig>();
+            // The skin is only loaded when the game starts
+            let restart = if game_config.skin == world.read_resource::<Skin>().name { "" } else { " (on restart)" };
+            let randomizer = match game_config.randomizer {
+                RandomizerKind::Stream => "Random",
+                RandomizerKind::Bag => "7-Bag",
+            };
+            vec![
+                format!("Ghost: {}", if game_config.ghost { "On" } else { "Off" }),
+                format!("Preview: {}", game_config.preview_count),
+                format!("Randomizer: {}", randomizer),
+                format!("Skin: {}{}", game_config.skin, restart),
+                format!("DAS: {} ms", handling.das),
+                format!("ARR: {} ms", handling.arr),
+                format!("DAS cut: {} ms", handling.das_cut),
+                "Back".to_string(),
+            ]
+        };
+        let menu = self.menu.as_ref().unwrap();
+        for (i, text) in texts.into_iter().enumerate() {
+            menu.set_text(world, i, text);
+        }
+    }
+}
+
+impl SimpleState for SettingsState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+        self.input_reader = Some(world.write_resource::<EventChannel<InputEvent<String>>>().register_reader());
+
+        let font = world.read_resource::<FontHandle>().clone();
+        let title = create_text(world, &font, "settings_title", "SETTINGS".to_string(), Anchor::Middle, (0., 200.), 40.);
+        let hint = "Left/Right: change  Esc: back".to_string();
+        let hint = create_text(world, &font, "settings_hint", hint, Anchor::Middle, (0., -200.), 16.);
+        self.ui_entities.extend(vec![title, hint]);
+
+        let options = [""; BACK + 1];
+        self.menu = Some(MenuList::create(world, &font, "settings", &options, 120.));
+        self.refresh(world);
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        save_settings(data.world);
+        if let Some(menu) = self.menu.take() {
+            self.ui_entities.extend(menu.entities);
+        }
+        data.world.delete_entities(&self.ui_entities).expect("Failed to remove the settings");
+        self.ui_entities.clear();
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        for action in read_actions(data.world, self.input_reader.as_mut().unwrap()) {
+            let selected = self.menu.as_ref().unwrap().selected;
+            match action.as_str() {
+                "back" => return Trans::Pop,
+                "left" => self.change(data.world, selected, -1),
+                "right" => self.change(data.world, selected, 1),
+                _ => match self.menu.as_mut().unwrap().handle_action(data.world, &action) {
+                    Some(BACK) => return Trans::Pop,
+                    Some(option) => self.change(data.world, option, 1),
+                    None => (),
+                },
+            }
+        }
+        Trans::None
+    }
+}
+
+/// Moves a time in milliseconds by steps of 10 ms without going below 0
+fn step_millis(millis: u64, step: i64) -> u64 {
+    (millis as i64 + step * 10).max(0) as u64
+}
+
+/// Writes the changed settings to the config files
+/// The game config is read from its file again, so that a seed passed on the command line is
+/// not saved along with it
+fn save_settings(world: &World) {
+    let game_config = world.read_resource::<GameConfig>();
+    let game_config_path = application_dir("resources/game_config.ron")
+        .expect("Failed to find the directory of the executable");
+    let mut saved = GameConfig::load(&game_config_path);
+    saved.ghost = game_config.ghost;
+    saved.preview_count = game_config.preview_count;
+    saved.randomizer = game_config.randomizer;
+    saved.skin = game_config.skin.clone();
+    if let Err(error) = saved.write(&game_config_path) {
+        log::warn!("Failed to save the game config: {}", error);
+    }
+
+    let handling_config_path = application_dir("resources/handling_config.ron")
+        .expect("Failed to find the directory of the executable");
+    if let Err(error) = world.read_resource::<HandlingConfig>().write(&handling_config_path) {
+        log::warn!("Failed to save the handling config: {}", error);
+    }
+}
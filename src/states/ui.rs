@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use amethyst::ecs::Entity;
+use amethyst::input::InputEvent;
+use amethyst::prelude::*;
+use amethyst::shrev::{EventChannel, ReaderId};
+use amethyst::ui::{Anchor, FontHandle, UiText, UiTransform};
+
+use crate::skin::Skin;
+
+/// Creates a line of text
+/// x/y: offset of the text's center from the anchor point in the window
+pub fn create_text(
+    world: &mut World,
+    font: &FontHandle,
+    id: &str,
+    text: String,
+    anchor: Anchor,
+    (x, y): (f32, f32),
+    font_size: f32,
+) -> Entity {
+    let transform = UiTransform::new(
+        id.to_string(), anchor,
+        x, y, 1., 300., font_size * 1.5, 0,
+    );
+    let color = world.read_resource::<Skin>().palette.text;
+    world
+        .create_entity()
+        .with(transform)
+        .with(UiText::new(font.clone(), text, color, font_size))
+        .build()
+}
+
+/// Returns all actions that have been pressed since the last call
+pub fn read_actions(world: &World, reader: &mut ReaderId<InputEvent<String>>) -> Vec<String> {
+    world.read_resource::<EventChannel<InputEvent<String>>>()
+        .read(reader)
+        .filter_map(|event| match event {
+            InputEvent::ActionPressed(action) => Some(action.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Formats a duration as minutes, seconds and hundredths of a second (m:ss.hh)
+pub fn format_time(time: Duration) -> String {
+    let seconds = time.as_secs();
+    format!("{}:{:02}.{:02}", seconds / 60, seconds % 60, time.subsec_millis() / 10)
+}
+
+
+/// A vertical list of options, one of which is highlighted
+/// The selection is moved with "up" and "down" and chosen with "confirm"
+pub struct MenuList {
+    pub entities: Vec<Entity>,
+    pub selected: usize,
+}
+
+impl MenuList {
+    /// Creates one line of text per option, centered in the window and starting at the given height
+    pub fn create(world: &mut World, font: &FontHandle, id: &str, options: &[&str], y: f32) -> MenuList {
+        let entities = options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| {
+                let id = format!("{}_{}", id, i);
+                create_text(world, font, &id, option.to_string(), Anchor::Middle, (0., y - 40. * i as f32), 24.)
+            })
+            .collect();
+        let menu = MenuList { entities, selected: 0 };
+        menu.highlight(world);
+        menu
+    }
+
+    /// Moves the selection according to the action and returns the index of the chosen option
+    /// once "confirm" has been pressed
+    pub fn handle_action(&mut self, world: &mut World, action: &str) -> Option<usize> {
+        let count = self.entities.len();
+        match action {
+            "up" => self.selected = (self.selected + count - 1) % count,
+            "down" => self.selected = (self.selected + 1) % count,
+            "confirm" => return Some(self.selected),
+            _ => return None,
+        }
+        self.highlight(world);
+        None
+    }
+
+    pub fn select(&mut self, world: &mut World, index: usize) {
+        self.selected = index.min(self.entities.len() - 1);
+        self.highlight(world);
+    }
+
+    /// Replaces the text of an option
+    pub fn set_text(&self, world: &mut World, index: usize, text: String) {
+        if let Some(ui_text) = world.write_storage::<UiText>().get_mut(self.entities[index]) {
+            ui_text.text = text;
+        }
+    }
+
+    fn highlight(&self, world: &mut World) {
+        let skin = world.read_resource::<Skin>();
+        let mut texts = world.write_storage::<UiText>();
+        for (i, entity) in self.entities.iter().enumerate() {
+            if let Some(text) = texts.get_mut(*entity) {
+                text.color = if i == self.selected { skin.palette.selected } else { skin.palette.text };
+            }
+        }
+    }
+}
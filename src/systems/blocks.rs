@@ -0,0 +1,149 @@
+use amethyst::ecs::{Entities, Entity, Join, Read, ReadExpect, Resources, System, SystemData, WriteStorage};
+use amethyst::renderer::{SpriteRender, SpriteSheetHandle};
+use amethyst::shrev::{EventChannel, ReaderId};
+
+use crate::components::{Block, write_active_piece};
+use crate::constants::GHOST_SPRITE;
+use crate::tetris_core::game::{Game, GameEvent};
+use crate::tetris_core::piece::{ActivePiece, Tetrominos};
+
+/// System which keeps the blocks of the play field in sync with the game
+/// Creates the blocks of every new piece, moves them along with the piece in play and removes
+/// them again when the piece is held or its rows are cleared
+pub struct BlockSystem {
+    channel_reader: Option<ReaderId<GameEvent>>,
+}
+
+impl BlockSystem {
+    pub fn new() -> BlockSystem {
+        BlockSystem {channel_reader: None}
+    }
+}
+
+impl <'a> System<'a> for BlockSystem {
+    type SystemData = (
+        WriteStorage<'a, Block>,
+        WriteStorage<'a, SpriteRender>,
+        ReadExpect<'a, Game>,
+        ReadExpect<'a, SpriteSheetHandle>,
+        Read<'a, EventChannel<GameEvent>>,
+        Entities<'a>,
+    );
+
+    fn run(&mut self, (
+        mut blocks,
+        mut sprite_render,
+        game,
+        sprite_handle,
+        channel,
+        entities
+    ): Self::SystemData) {
+        // The events have to be applied in order, a piece may e.g. spawn and be held in the same step
+        for event in channel.read(&mut self.channel_reader.as_mut().unwrap()) {
+            match event {
+                GameEvent::Spawned(piece) => {
+                    create_piece_blocks(piece, &entities, &mut blocks, &mut sprite_render, &sprite_handle);
+                },
+                GameEvent::Held(_) => remove_falling_blocks(&entities, &mut blocks),
+                GameEvent::Locked(piece) => {
+                    write_active_piece(piece, &mut blocks);
+                    for block in (&mut blocks).join() {
+                        block.falling = false;
+                    }
+                },
+                GameEvent::LinesCleared(rows) => clear_rows(rows, &entities, &mut blocks),
+                GameEvent::GameOver(_) => (),
+            }
+        }
+
+        if let Some(piece) = &game.active {
+            write_active_piece(piece, &mut blocks);
+        }
+    }
+
+    fn setup(&mut self, res: &mut Resources) {
+        Self::SystemData::setup(res);
+        self.channel_reader = Some(res.fetch_mut::<EventChannel<GameEvent>>().register_reader());
+    }
+}
+
+
+/// Adds the blocks of the piece to the world as falling blocks
+pub fn create_piece_blocks(
+    piece: &ActivePiece,
+    entities: &Entities,
+    blocks: &mut WriteStorage<Block>,
+    sprite_render: &mut WriteStorage<SpriteRender>,
+    sprite_handle: &SpriteSheetHandle,
+) {
+    for (x, y) in &piece.blocks {
+        entities
+            .build_entity()
+            .with(Block {
+                x: *x,
+                y: *y,
+                falling: true,
+                initialized: false,
+                rotation: piece.rotation,
+                piece: piece.piece,
+            }, blocks)
+            .with(get_sprite_render(&piece.piece, sprite_handle), sprite_render)
+            .build();
+    }
+}
+
+/// Removes the falling blocks right away, so that they are gone before the blocks of the next
+/// piece are created
+fn remove_falling_blocks(entities: &Entities, blocks: &mut WriteStorage<Block>) {
+    let falling: Vec<Entity> = (&**entities, &*blocks).join()
+        .filter(|(_, block)| block.falling)
+        .map(|(entity, _)| entity)
+        .collect();
+    for entity in falling {
+        blocks.remove(entity);
+        entities.delete(entity).expect("Failed to delete a held block");
+    }
+}
+
+/// Removes the locked blocks of the cleared rows and moves the blocks above them down to close
+/// the gap
+fn clear_rows(rows: &[i32], entities: &Entities, blocks: &mut WriteStorage<Block>) {
+    for (entity, block) in (&**entities, &mut *blocks).join() {
+        if block.falling {
+            continue;
+        }
+        if rows.contains(&block.y) {
+            entities.delete(entity).expect("Failed to delete a cleared block");
+        } else {
+            // Move the block down by one tile for every cleared row beneath it
+            let rows_below = rows.iter().filter(|y| **y < block.y).count() as i32;
+            block.y -= rows_below * 2;
+        }
+    }
+}
+
+
+/// Returns a SpriteRender component which corresponds to the correct tetromino
+pub fn get_sprite_render(piece: &Tetrominos, sprite_resource: &SpriteSheetHandle) -> SpriteRender {
+    let sprite_number = match piece {
+        Tetrominos::I => 0,
+        Tetrominos::J => 1,
+        Tetrominos::L => 2,
+        Tetrominos::O => 3,
+        Tetrominos::S => 4,
+        Tetrominos::T => 5,
+        Tetrominos::Z => 6,
+    };
+    SpriteRender {
+        sprite_sheet: (*sprite_resource).clone(),
+        sprite_number,
+    }
+}
+
+/// Returns a SpriteRender component for a ghost block
+pub fn get_ghost_sprite_render(sprite_resource: &SpriteSheetHandle) -> SpriteRender {
+    SpriteRender {
+        sprite_sheet: (*sprite_resource).clone(),
+        sprite_number: GHOST_SPRITE,
+    }
+}
@@ -0,0 +1,84 @@
+use amethyst::core::timing::Time;
+use amethyst::ecs::{Read, Resources, System, SystemData, Write, WriteExpect};
+use amethyst::input::InputHandler;
+use amethyst::shrev::{EventChannel, ReaderId};
+
+use crate::components::GameOver;
+use crate::systems::key_update::KeyEvent;
+use crate::tetris_core::game::{Game, GameEvent, Input};
+use crate::tetris_core::handling::AutoShift;
+
+/// System which advances the game by one step every frame
+/// The KeyEvents of the frame are passed to the game as inputs, and everything that happened is
+/// sent out as GameEvents for the systems which keep the entities in sync with the game
+pub struct GameSystem {
+    channel_reader: Option<ReaderId<KeyEvent>>,
+}
+
+impl GameSystem {
+    pub fn new() -> GameSystem {
+        GameSystem {channel_reader: None}
+    }
+}
+
+impl <'a> System<'a> for GameSystem {
+    type SystemData = (
+        WriteExpect<'a, Game>,
+        WriteExpect<'a, AutoShift>,
+        Write<'a, GameOver>,
+        Read<'a, InputHandler<String, String>>,
+        Read<'a, EventChannel<KeyEvent>>,
+        Write<'a, EventChannel<GameEvent>>,
+        Read<'a, Time>,
+    );
+
+    fn run(&mut self, (
+        mut game,
+        mut auto_shift,
+        mut game_over,
+        input,
+        key_channel,
+        mut game_channel,
+        time
+    ): Self::SystemData) {
+        let mut inputs: Vec<Input> = key_channel.read(&mut self.channel_reader.as_mut().unwrap())
+            .map(key_event_to_input)
+            .collect();
+        // Soft drop lasts for as long as the descend key is held down
+        let descending = input.action_is_down(&"descend".to_string()).unwrap_or(false);
+        if descending && !inputs.contains(&Input::SoftDrop) {
+            inputs.push(Input::SoftDrop);
+        }
+
+        let events = game.step(&inputs, time.delta_time());
+        for event in &events {
+            match event {
+                // Every new piece starts with a DAS cut
+                GameEvent::Spawned(_) => auto_shift.cut(),
+                GameEvent::GameOver(reason) => game_over.reason = Some(*reason),
+                _ => (),
+            }
+        }
+        game_channel.iter_write(events);
+    }
+
+    fn setup(&mut self, res: &mut Resources) {
+        Self::SystemData::setup(res);
+        self.channel_reader = Some(res.fetch_mut::<EventChannel<KeyEvent>>().register_reader());
+    }
+}
+
+fn key_event_to_input(event: &KeyEvent) -> Input {
+    match event {
+        KeyEvent::Left => Input::Left,
+        KeyEvent::Right => Input::Right,
+        KeyEvent::LeftToWall => Input::LeftToWall,
+        KeyEvent::RightToWall => Input::RightToWall,
+        KeyEvent::RotateClockwise => Input::RotateClockwise,
+        KeyEvent::RotateCounterClockwise => Input::RotateCounterClockwise,
+        KeyEvent::Rotate180 => Input::Rotate180,
+        KeyEvent::Descend => Input::SoftDrop,
+        KeyEvent::Drop => Input::HardDrop,
+        KeyEvent::Hold => Input::Hold,
+    }
+}
@@ -0,0 +1,90 @@
+use amethyst::core::Transform;
+use amethyst::ecs::{Entities, Entity, Join, ReadExpect, System, WriteStorage};
+use amethyst::renderer::{Rgba, SpriteRender, SpriteSheetHandle, Transparent};
+
+use crate::components::GhostBlock;
+use crate::config::GameConfig;
+use crate::skin::Skin;
+use crate::systems::blocks::get_ghost_sprite_render;
+use crate::tetris_core::game::Game;
+use crate::tetris_core::piece::Tetrominos;
+
+/// System which keeps the ghost blocks at the position the falling piece would land at
+/// The ghost blocks are placed on the screen by the position update system
+pub struct GhostSystem {
+    piece: Option<Tetrominos>,
+}
+
+impl GhostSystem {
+    pub fn new() -> GhostSystem {
+        GhostSystem {piece: None}
+    }
+}
+
+impl<'a> System<'a> for GhostSystem {
+    type SystemData = (
+        WriteStorage<'a, GhostBlock>,
+        WriteStorage<'a, SpriteRender>,
+        WriteStorage<'a, Transform>,
+        WriteStorage<'a, Transparent>,
+        WriteStorage<'a, Rgba>,
+        ReadExpect<'a, Game>,
+        ReadExpect<'a, GameConfig>,
+        ReadExpect<'a, Skin>,
+        ReadExpect<'a, SpriteSheetHandle>,
+        Entities<'a>,
+    );
+
+    fn run(&mut self, (
+        mut ghost_blocks,
+        mut sprite_render,
+        mut transforms,
+        mut transparent,
+        mut tints,
+        game,
+        config,
+        skin,
+        sprite_handle,
+        entities
+    ): Self::SystemData) {
+        let ghost_piece = if config.ghost {
+            game.active.clone().map(|mut piece| {
+                piece.hard_drop(&game.board);
+                piece
+            })
+        } else {
+            None
+        };
+
+        let ghosts: Vec<Entity> = (&*entities, &ghost_blocks).join().map(|(entity, _)| entity).collect();
+        let piece_type = ghost_piece.as_ref().map(|piece| piece.piece);
+        let block_count = ghost_piece.as_ref().map_or(0, |piece| piece.blocks.len());
+
+        // Recreate the ghost blocks whenever the type of the falling piece changes
+        if piece_type != self.piece || ghosts.len() != block_count {
+            for entity in ghosts {
+                ghost_blocks.remove(entity);
+                entities.delete(entity).expect("Failed to delete a ghost block");
+            }
+            if let Some(piece) = &ghost_piece {
+                let tint = skin.palette.ghost;
+                for (x, y) in &piece.blocks {
+                    entities
+                        .build_entity()
+                        .with(GhostBlock { x: *x, y: *y }, &mut ghost_blocks)
+                        .with(get_ghost_sprite_render(&sprite_handle), &mut sprite_render)
+                        .with(Transform::default(), &mut transforms)
+                        .with(Transparent, &mut transparent)
+                        .with(Rgba(tint[0], tint[1], tint[2], tint[3]), &mut tints)
+                        .build();
+                }
+            }
+            self.piece = piece_type;
+        } else if let Some(piece) = &ghost_piece {
+            for (ghost, (x, y)) in (&mut ghost_blocks).join().zip(piece.blocks.iter()) {
+                ghost.x = *x;
+                ghost.y = *y;
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,22 @@
+use amethyst::ecs::{Read, System, Write};
+
+use crate::components::{GameOver, GameStats};
+use crate::tetris_core::game::GameOverReason;
+use crate::tetris_core::mode::GameMode;
+
+/// System which ends the game once the goal of the game mode has been reached
+pub struct GoalSystem;
+
+impl<'a> System<'a> for GoalSystem {
+    type SystemData = (
+        Read<'a, GameMode>,
+        Read<'a, GameStats>,
+        Write<'a, GameOver>,
+    );
+
+    fn run(&mut self, (mode, stats, mut game_over): Self::SystemData) {
+        if !game_over.is_over() && mode.is_complete(stats.lines, stats.time) {
+            game_over.reason = Some(GameOverReason::Finished);
+        }
+    }
+}
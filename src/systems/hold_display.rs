@@ -0,0 +1,87 @@
+use amethyst::core::Transform;
+use amethyst::ecs::{Component, Entities, Join, Read, ReadExpect, System, WriteStorage};
+use amethyst::renderer::{SpriteRender, SpriteSheetHandle};
+
+use crate::components::{HoldBlock, LayoutConfig};
+use crate::systems::blocks::get_sprite_render;
+use crate::tetris_core::game::Game;
+use crate::tetris_core::piece::{get_layout, Tetrominos};
+
+/// System which draws the held piece next to the play field
+/// The blocks are only recreated when the held piece changes
+pub struct HoldDisplaySystem {
+    shown: Option<Tetrominos>,
+}
+
+impl HoldDisplaySystem {
+    pub fn new() -> HoldDisplaySystem {
+        HoldDisplaySystem {shown: None}
+    }
+}
+
+impl<'a> System<'a> for HoldDisplaySystem {
+    type SystemData = (
+        WriteStorage<'a, HoldBlock>,
+        WriteStorage<'a, SpriteRender>,
+        WriteStorage<'a, Transform>,
+        ReadExpect<'a, Game>,
+        Read<'a, LayoutConfig>,
+        ReadExpect<'a, SpriteSheetHandle>,
+        Entities<'a>,
+    );
+
+    fn run(&mut self, (
+        mut hold_blocks,
+        mut sprite_render,
+        mut transforms,
+        game,
+        layout,
+        sprite_handle,
+        entities
+    ): Self::SystemData) {
+        let hold = &game.hold;
+        if hold.piece == self.shown {
+            return;
+        }
+
+        for (entity, _) in (&*entities, &hold_blocks).join() {
+            entities.delete(entity).expect("Failed to delete a hold block");
+        }
+        if let Some(piece) = hold.piece {
+            let position = (layout.hold_x, layout.hold_y);
+            create_display_piece(piece, position, HoldBlock, &layout, &entities, &mut hold_blocks,
+                                 &mut sprite_render, &mut transforms, &sprite_handle);
+        }
+        self.shown = hold.piece;
+    }
+}
+
+/// Adds blocks showing the piece outside of the play field to the world
+/// position: screen position of the piece's spawning block
+/// marker: component marking the blocks so that they can be found again
+pub fn create_display_piece<T: Component + Clone>(
+    piece: Tetrominos,
+    position: (i32, i32),
+    marker: T,
+    layout: &LayoutConfig,
+    entities: &Entities,
+    markers: &mut WriteStorage<T>,
+    sprite_render: &mut WriteStorage<SpriteRender>,
+    transforms: &mut WriteStorage<Transform>,
+    sprite_handle: &SpriteSheetHandle,
+) {
+    for (x_offset, y_offset) in get_layout(&piece) {
+        let mut transform = Transform::default();
+        transform.set_translation_xyz(
+            (position.0 + x_offset * layout.tile_size) as f32,
+            (position.1 + y_offset * layout.tile_size) as f32,
+            0.,
+        );
+        entities
+            .build_entity()
+            .with(marker.clone(), markers)
+            .with(get_sprite_render(&piece, sprite_handle), sprite_render)
+            .with(transform, transforms)
+            .build();
+    }
+}
\ No newline at end of file
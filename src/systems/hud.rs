@@ -0,0 +1,55 @@
+use amethyst::ecs::{Join, Read, ReadExpect, ReadStorage, System, WriteStorage};
+use amethyst::renderer::ScreenDimensions;
+use amethyst::ui::{UiText, UiTransform};
+
+use crate::components::{GameStats, HudStat, HudText, LayoutConfig};
+use crate::constants::{VIEW_HEIGHT, VIEW_WIDTH};
+use crate::states::ui::format_time;
+use crate::tetris_core::game::Game;
+
+/// System which keeps the text of the HUD up to date and places it according to the layout
+/// The layout is given in view coordinates, so the HUD is scaled to the size of the window
+pub struct HudSystem;
+
+impl<'a> System<'a> for HudSystem {
+    type SystemData = (
+        ReadStorage<'a, HudText>,
+        WriteStorage<'a, UiText>,
+        WriteStorage<'a, UiTransform>,
+        ReadExpect<'a, Game>,
+        Read<'a, GameStats>,
+        Read<'a, LayoutConfig>,
+        ReadExpect<'a, ScreenDimensions>,
+    );
+
+    fn run(&mut self, (
+        hud_texts,
+        mut ui_texts,
+        mut ui_transforms,
+        game,
+        stats,
+        layout,
+        screen
+    ): Self::SystemData) {
+        let scale_x = screen.width() / VIEW_WIDTH;
+        let scale_y = screen.height() / VIEW_HEIGHT;
+
+        for (hud_text, ui_text, transform) in (&hud_texts, &mut ui_texts, &mut ui_transforms).join() {
+            ui_text.text = match hud_text.stat {
+                HudStat::Score => format!("Score: {}", game.score.points),
+                HudStat::Level => format!("Level: {}", game.level.level),
+                HudStat::Lines => format!("Lines: {}", stats.lines),
+                HudStat::Time => format!("Time: {}", format_time(stats.time)),
+                HudStat::PiecesPerSecond => {
+                    let seconds = stats.time.as_secs() as f32 + stats.time.subsec_millis() as f32 / 1000.;
+                    let pps = if seconds > 0. { stats.pieces as f32 / seconds } else { 0. };
+                    format!("PPS: {:.2}", pps)
+                },
+            };
+
+            let y = layout.hud_y - hud_text.line * layout.hud_spacing;
+            transform.local_x = layout.hud_x as f32 * scale_x;
+            transform.local_y = y as f32 * scale_y;
+        }
+    }
+}
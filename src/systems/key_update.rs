@@ -1,15 +1,19 @@
 use std::collections::HashMap;
-use std::time::Duration;
 
-use amethyst::core::{timing::Time, Transform};
-use amethyst::ecs::{Entities, Join, Read, ReadStorage, Resources, System, SystemData, Write, WriteStorage};
+use amethyst::core::timing::Time;
+use amethyst::ecs::{Read, Resources, System, SystemData, Write, WriteExpect};
 use amethyst::input::InputHandler;
 use amethyst::shrev::EventChannel;
 
-use crate::constants::{INPUT_TYPES, KEY_REPEAT_THRESHOLD, KEY_REPEAT_TIME};
+use crate::constants::INPUT_TYPES;
+use crate::tetris_core::handling::{AutoShift, Direction, Shift};
 
+/// System which turns the player's input into KeyEvents
+/// Every action is sent once when its key is pressed, left and right are then repeated by the
+/// auto shift
 pub struct KeyUpdateSystem {
-    key_data: HashMap<String, (bool, Duration, Duration)>,
+    // Whether the key of an action was held down during the last frame
+    key_data: HashMap<String, bool>,
 }
 
 impl KeyUpdateSystem {
@@ -22,64 +26,46 @@ impl <'a> System<'a> for KeyUpdateSystem {
     type SystemData = (
         Read<'a, InputHandler<String, String>>,
         Write<'a, EventChannel<KeyEvent>>,
+        WriteExpect<'a, AutoShift>,
         Read<'a, Time>,
     );
 
-    fn run(&mut self, (input, mut channel, time): Self::SystemData) {
-        let time_delta = time.delta_time();
-        // Create a list of all actions that are currently incoming
-        let mut actions: Vec<&str> = Vec::new();
-        for input_type in &INPUT_TYPES {
-            if input.action_is_down(&input_type.to_string())
-                .expect("Invalid key input type: Check whether the const array of key definitions and binding_config.ron are identical") {
-                actions.push(input_type);
-            }
-        }
-
+    fn run(&mut self, (
+        input,
+        mut channel,
+        mut auto_shift,
+        time
+    ): Self::SystemData) {
         // Create a Vec which will store which KeyEvents will be written to the output channel
         let mut write_out: Vec<KeyEvent> = Vec::new();
 
         for input_type in &INPUT_TYPES {
-            // Get the information corresponding to the input_type being checked from the HashMap
-            let (active, total_time, repeat_time) = self.key_data.get_mut(&input_type.to_string()).unwrap();
-            match (actions.contains(input_type), &active) {
-                // key is pressed and was pressed down before
-                (true, true) => {
-//                    println!("{:?}, {:?}", total_time, repeat_time);
-                    // Check whether the minimum time for the key to start repeating has been reached
-                    if total_time > &mut KEY_REPEAT_THRESHOLD {
-                        // Check whether the time controlling the repeat frequency has been reached
-                        if repeat_time > &mut KEY_REPEAT_TIME {
-                            // Send out a KeyEvent and reset the repeat duration
-                            write_out.push(str_to_key_event(input_type));
-                            *repeat_time = Duration::from_secs(0);
-                        } else {
-                            // Increase the repeat duration
-                            *repeat_time += time_delta;
-                        }
-                    } else {
-                        // Increase the total duration the key has been held down
-                        *total_time += time_delta;
-                    }
-                },
-                // key is pressed but wasn't pressed down before
-                (true, false) => {
-                    // Send out a KeyEvent and mark the key as having been activated
-                    *active = true;
-                    write_out.push(str_to_key_event(input_type));
+            let is_down = input.action_is_down(&input_type.to_string())
+                .expect("Invalid key input type: Check whether the const array of key definitions and binding_config.ron are identical");
+            let was_down = self.key_data.insert(input_type.to_string(), is_down).unwrap_or(false);
+
+            match (is_down, was_down, str_to_direction(input_type)) {
+                // a direction was pressed, which takes priority over the other direction
+                (true, false, Some(direction)) => {
+                    write_out.push(shift_to_key_event(auto_shift.press(direction)));
                 },
-                // key is not pressed but was previously
-                (false, true) => {
-                    // Reset the values corresponding to the key
-                    *active = false;
-                    *total_time = Duration::from_secs(0);
-                    *repeat_time = Duration::from_millis(1) + KEY_REPEAT_TIME;
+                // a direction was released, the other direction takes over if it is still held
+                (false, true, Some(direction)) => auto_shift.release(direction),
+                // any other key was pressed
+                (true, false, None) => {
+                    let event = str_to_key_event(input_type);
+                    if event.is_rotation() {
+                        auto_shift.cut();
+                    }
+                    write_out.push(event);
                 },
-                // key neither is nor was pressed
-                (false, false) => continue
+                _ => continue
             }
         }
 
+        // Repeat the held direction, every frame with the exact time that has passed
+        write_out.extend(auto_shift.update(time.delta_time()).into_iter().map(shift_to_key_event));
+
         channel.iter_write(write_out);
     }
 
@@ -87,11 +73,7 @@ impl <'a> System<'a> for KeyUpdateSystem {
         Self::SystemData::setup(res);
         // Generate a default value for all input types
         for input in &INPUT_TYPES {
-            self.key_data.insert(input.to_string(), (
-                false,
-                Duration::from_secs(0),
-                Duration::from_secs(1) + KEY_REPEAT_TIME
-            ));
+            self.key_data.insert(input.to_string(), false);
         }
     }
 }
@@ -102,17 +84,50 @@ fn str_to_key_event(key_string: &str) -> KeyEvent {
         "left" => KeyEvent::Left,
         "right" => KeyEvent::Right,
         "rotate"=> KeyEvent::RotateClockwise,
+        "rotate_ccw" => KeyEvent::RotateCounterClockwise,
+        "rotate_180" => KeyEvent::Rotate180,
         "descend" => KeyEvent::Descend,
         "drop" => KeyEvent::Drop,
+        "hold" => KeyEvent::Hold,
         _ => panic!("invalid key input type received")
     }
 }
 
+fn str_to_direction(key_string: &str) -> Option<Direction> {
+    match key_string {
+        "left" => Some(Direction::Left),
+        "right" => Some(Direction::Right),
+        _ => None
+    }
+}
+
+fn shift_to_key_event(shift: Shift) -> KeyEvent {
+    match shift {
+        Shift::Step(Direction::Left) => KeyEvent::Left,
+        Shift::Step(Direction::Right) => KeyEvent::Right,
+        Shift::ToWall(Direction::Left) => KeyEvent::LeftToWall,
+        Shift::ToWall(Direction::Right) => KeyEvent::RightToWall,
+    }
+}
+
 pub enum KeyEvent {
     Left,
     Right,
+    LeftToWall,
+    RightToWall,
     RotateClockwise,
     RotateCounterClockwise,
+    Rotate180,
     Descend,
     Drop,
-}
\ No newline at end of file
+    Hold,
+}
+
+impl KeyEvent {
+    pub fn is_rotation(&self) -> bool {
+        match self {
+            KeyEvent::RotateClockwise | KeyEvent::RotateCounterClockwise | KeyEvent::Rotate180 => true,
+            _ => false,
+        }
+    }
+}
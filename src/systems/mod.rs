@@ -1,8 +1,10 @@
-pub mod clear;
-pub mod spawn;
-pub mod gravity;
+pub mod blocks;
+pub mod game;
+pub mod ghost;
+pub mod goal;
+pub mod hold_display;
+pub mod hud;
 pub mod pos_update;
-pub mod translation;
-pub mod timing;
+pub mod preview;
 pub mod key_update;
-pub mod rotation;
\ No newline at end of file
+pub mod stats;
\ No newline at end of file
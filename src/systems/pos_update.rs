@@ -1,7 +1,7 @@
 use amethyst::core::Transform;
-use amethyst::ecs::{Entities, Join, Read, System, WriteStorage};
+use amethyst::ecs::{Entities, Join, Read, ReadStorage, System, WriteStorage};
 
-use crate::components::{Block, GravityTimer, LayoutConfig, RandomStream, RotationCenter, SpawnTimer};
+use crate::components::{Block, GhostBlock, LayoutConfig};
 
 // System which updates the positions of all blocks on the screen based on their current coordinates
 // Also adds a transform component to blocks that have not been fully initialized yet
@@ -10,6 +10,7 @@ pub struct PositionUpdateSystem;
 impl<'a> System<'a> for PositionUpdateSystem {
     type SystemData = (
         WriteStorage<'a, Block>,
+        ReadStorage<'a, GhostBlock>,
         WriteStorage<'a, Transform>,
         Read<'a, LayoutConfig>,
         Entities<'a>
@@ -18,6 +19,7 @@ impl<'a> System<'a> for PositionUpdateSystem {
 
     fn run(&mut self, (
         mut blocks,
+        ghost_blocks,
         mut transforms,
         layout_config,
         entities):
@@ -34,6 +36,12 @@ impl<'a> System<'a> for PositionUpdateSystem {
             let xy = xy_from_coordinates(&layout_config, block.x, block.y);
             transform.set_translation_xyz(xy.0, xy.1, 0.);
         }
+
+        // Ghost blocks are drawn behind the falling blocks
+        for (ghost, transform) in (&ghost_blocks, &mut transforms).join() {
+            let xy = xy_from_coordinates(&layout_config, ghost.x, ghost.y);
+            transform.set_translation_xyz(xy.0, xy.1, -0.5);
+        }
     }
 }
 
@@ -0,0 +1,64 @@
+use amethyst::core::Transform;
+use amethyst::ecs::{Entities, Join, Read, ReadExpect, System, WriteExpect, WriteStorage};
+use amethyst::renderer::{SpriteRender, SpriteSheetHandle};
+
+use crate::components::{LayoutConfig, PreviewBlock};
+use crate::config::GameConfig;
+use crate::systems::hold_display::create_display_piece;
+use crate::tetris_core::game::Game;
+use crate::tetris_core::piece::Tetrominos;
+
+/// System which draws the next pieces of the randomizer next to the play field
+/// The blocks are only recreated when the upcoming pieces change
+pub struct PreviewDisplaySystem {
+    shown: Vec<Tetrominos>,
+}
+
+impl PreviewDisplaySystem {
+    pub fn new() -> PreviewDisplaySystem {
+        PreviewDisplaySystem {shown: Vec::new()}
+    }
+}
+
+impl<'a> System<'a> for PreviewDisplaySystem {
+    type SystemData = (
+        WriteStorage<'a, PreviewBlock>,
+        WriteStorage<'a, SpriteRender>,
+        WriteStorage<'a, Transform>,
+        WriteExpect<'a, Game>,
+        ReadExpect<'a, GameConfig>,
+        Read<'a, LayoutConfig>,
+        ReadExpect<'a, SpriteSheetHandle>,
+        Entities<'a>,
+    );
+
+    fn run(&mut self, (
+        mut preview_blocks,
+        mut sprite_render,
+        mut transforms,
+        mut game,
+        config,
+        layout,
+        sprite_handle,
+        entities
+    ): Self::SystemData) {
+        // Only as many pieces are shown as fit next to the play field
+        let upcoming = game.randomizer.preview(config.preview_count.min(layout.max_preview()));
+        // The blocks are also recreated if they have been removed, e.g. by restarting the game
+        let block_count = (&preview_blocks).join().count();
+        if upcoming == self.shown && block_count == upcoming.len() * 4 {
+            return;
+        }
+
+        for (entity, _) in (&*entities, &preview_blocks).join() {
+            entities.delete(entity).expect("Failed to delete a preview block");
+        }
+        let spacing = layout.preview_spacing(upcoming.len());
+        for (i, piece) in upcoming.iter().enumerate() {
+            let position = (layout.preview_x, layout.preview_y - i as i32 * spacing);
+            create_display_piece(*piece, position, PreviewBlock, &layout, &entities, &mut preview_blocks,
+                                 &mut sprite_render, &mut transforms, &sprite_handle);
+        }
+        self.shown = upcoming;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+use amethyst::core::timing::Time;
+use amethyst::ecs::{Read, Resources, System, SystemData, Write};
+use amethyst::shrev::{EventChannel, ReaderId};
+
+use crate::components::GameStats;
+use crate::tetris_core::game::GameEvent;
+
+/// System which keeps the game statistics up to date with the time that has passed and the
+/// pieces locked and rows cleared by the game
+/// Pieces are only counted once they are locked, so the statistics only include placed pieces
+pub struct StatsSystem {
+    channel_reader: Option<ReaderId<GameEvent>>,
+}
+
+impl StatsSystem {
+    pub fn new() -> StatsSystem {
+        StatsSystem {channel_reader: None}
+    }
+}
+
+impl <'a> System<'a> for StatsSystem {
+    type SystemData = (
+        Write<'a, GameStats>,
+        Read<'a, EventChannel<GameEvent>>,
+        Read<'a, Time>,
+    );
+
+    fn run(&mut self, (mut stats, channel, time): Self::SystemData) {
+        stats.time += time.delta_time();
+        for event in channel.read(&mut self.channel_reader.as_mut().unwrap()) {
+            match event {
+                GameEvent::Locked(_) => stats.pieces += 1,
+                GameEvent::LinesCleared(rows) => stats.lines += rows.len() as u32,
+                _ => (),
+            }
+        }
+    }
+
+    fn setup(&mut self, res: &mut Resources) {
+        Self::SystemData::setup(res);
+        self.channel_reader = Some(res.fetch_mut::<EventChannel<GameEvent>>().register_reader());
+    }
+}
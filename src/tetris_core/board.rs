@@ -0,0 +1,168 @@
+use crate::constants::{ARENA_HEIGHT, ARENA_WIDTH, VISIBLE_HEIGHT};
+use crate::tetris_core::game::GameOverReason;
+use crate::tetris_core::piece::ActivePiece;
+
+/// Keeps track of which cells of the play field are occupied by locked blocks
+///
+/// Uses the same coordinates as the blocks, so every cell spans two units in each direction.
+/// Is filled whenever a piece is locked and emptied whenever rows are cleared.
+pub struct Board {
+    width: i32,
+    height: i32,
+    cells: Vec<bool>,
+}
+
+impl Board {
+    /// Returns whether a falling block could be placed at the given coordinates
+    /// Positions above the top of the arena are free as long as they are within the side walls
+    pub fn is_free(&self, x: i32, y: i32) -> bool {
+        if x < 0 || x > ARENA_WIDTH - 2 || y < 0 {
+            return false;
+        }
+        !self.is_occupied(x, y)
+    }
+
+    /// Returns whether a locked block occupies the given coordinates
+    pub fn is_occupied(&self, x: i32, y: i32) -> bool {
+        match self.index(x, y) {
+            Some(i) => self.cells[i],
+            None => false,
+        }
+    }
+
+    pub fn set_occupied(&mut self, x: i32, y: i32, occupied: bool) {
+        if let Some(i) = self.index(x, y) {
+            self.cells[i] = occupied;
+        }
+    }
+
+    /// Marks the cells of the piece as occupied
+    /// Returns a lock out if the piece was locked completely above the visible play field
+    pub fn lock(&mut self, piece: &ActivePiece) -> Option<GameOverReason> {
+        for (x, y) in &piece.blocks {
+            self.set_occupied(*x, *y, true);
+        }
+        if piece.blocks.iter().all(|(_, y)| *y >= VISIBLE_HEIGHT) {
+            Some(GameOverReason::LockOut)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the y coordinates of all completely filled rows in ascending order
+    pub fn full_rows(&self) -> Vec<i32> {
+        (0..self.height)
+            .filter(|row| (0..self.width).all(|column| self.cells[(row * self.width + column) as usize]))
+            .map(|row| row * 2)
+            .collect()
+    }
+
+    /// Removes the given rows and moves all rows above them down to close the gaps
+    pub fn clear_rows(&mut self, rows: &[i32]) {
+        let mut remaining: Vec<bool> = Vec::with_capacity(self.cells.len());
+        for row in 0..self.height {
+            if !rows.contains(&(row * 2)) {
+                let start = (row * self.width) as usize;
+                remaining.extend_from_slice(&self.cells[start..start + self.width as usize]);
+            }
+        }
+        remaining.resize(self.cells.len(), false);
+        self.cells = remaining;
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        let (column, row) = (x / 2, y / 2);
+        if x < 0 || y < 0 || column >= self.width || row >= self.height {
+            None
+        } else {
+            Some((row * self.width + column) as usize)
+        }
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        let width = ARENA_WIDTH / 2;
+        let height = ARENA_HEIGHT / 2;
+        Board { width, height, cells: vec![false; (width * height) as usize] }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tetris_core::piece::Tetrominos;
+
+    /// Fills the row with the given y coordinate, leaving out the column with the given x coordinate
+    fn fill_row(board: &mut Board, y: i32, gap: Option<i32>) {
+        for x in (0..ARENA_WIDTH).step_by(2) {
+            if Some(x) != gap {
+                board.set_occupied(x, y, true);
+            }
+        }
+    }
+
+    #[test]
+    fn walls_and_floor_are_not_free() {
+        let board = Board::default();
+        assert!(board.is_free(0, 0));
+        assert!(board.is_free(ARENA_WIDTH - 2, 0));
+        assert!(!board.is_free(-2, 0));
+        assert!(!board.is_free(ARENA_WIDTH, 0));
+        assert!(!board.is_free(0, -2));
+        // Above the arena is free as long as it is between the walls
+        assert!(board.is_free(0, ARENA_HEIGHT + 10));
+    }
+
+    #[test]
+    fn locked_blocks_are_not_free() {
+        let mut board = Board::default();
+        let mut piece = ActivePiece::spawn(Tetrominos::O);
+        piece.hard_drop(&board);
+        assert_eq!(board.lock(&piece), None);
+        for (x, y) in &piece.blocks {
+            assert!(board.is_occupied(*x, *y));
+            assert!(!board.is_free(*x, *y));
+        }
+    }
+
+    #[test]
+    fn locking_above_the_visible_field_is_a_lock_out() {
+        let mut board = Board::default();
+        let piece = ActivePiece::spawn(Tetrominos::I);
+        assert_eq!(board.lock(&piece), Some(GameOverReason::LockOut));
+
+        let mut board = Board::default();
+        let mut piece = ActivePiece::spawn(Tetrominos::I);
+        piece.hard_drop(&board);
+        assert_eq!(board.lock(&piece), None);
+    }
+
+    #[test]
+    fn full_rows_are_found_in_ascending_order() {
+        let mut board = Board::default();
+        fill_row(&mut board, 4, None);
+        fill_row(&mut board, 0, None);
+        fill_row(&mut board, 2, Some(6));
+        assert_eq!(board.full_rows(), vec![0, 4]);
+    }
+
+    #[test]
+    fn clearing_rows_moves_the_rows_above_down() {
+        let mut board = Board::default();
+        fill_row(&mut board, 0, None);
+        fill_row(&mut board, 2, Some(6));
+        fill_row(&mut board, 4, None);
+        board.set_occupied(8, 6, true);
+
+        board.clear_rows(&[0, 4]);
+
+        assert!(board.full_rows().is_empty());
+        assert!(board.is_occupied(0, 0));
+        assert!(!board.is_occupied(6, 0));
+        assert!(board.is_occupied(8, 2));
+        assert!(!board.is_occupied(0, 2));
+        assert!(!board.is_occupied(8, 6));
+    }
+}
@@ -0,0 +1,451 @@
+use std::time::Duration;
+
+use crate::tetris_core::board::Board;
+use crate::tetris_core::handling::{InitialRotation, InputBuffer};
+use crate::tetris_core::hold::HoldSlot;
+use crate::tetris_core::level::{GravityCurve, Level};
+use crate::tetris_core::piece::{ActivePiece, Tetrominos};
+use crate::tetris_core::randomizer::Randomizer;
+use crate::tetris_core::score::Score;
+use crate::tetris_core::phase::{Phase, PhaseTimer};
+use crate::tetris_core::timers::{GravityTimer, LockTimer};
+
+/// The inputs the player can make during a step of the game
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Input {
+    Left,
+    Right,
+    LeftToWall,
+    RightToWall,
+    RotateClockwise,
+    RotateCounterClockwise,
+    Rotate180,
+    SoftDrop,
+    HardDrop,
+    Hold,
+}
+
+/// Everything that happened during a step of the game, in the order it happened
+#[derive(Clone, PartialEq, Debug)]
+pub enum GameEvent {
+    // A new piece came into play
+    Spawned(ActivePiece),
+    // The piece was put into the hold slot and left play
+    Held(Tetrominos),
+    // The piece was locked in place at its final position
+    Locked(ActivePiece),
+    // The y coordinates of the cleared rows, in ascending order
+    LinesCleared(Vec<i32>),
+    GameOver(GameOverReason),
+}
+
+/// The ways in which a game can end
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GameOverReason {
+    // A new piece overlapped the stack when it was spawned
+    BlockOut,
+    // A piece was locked completely above the visible play field
+    LockOut,
+    // The goal of the game mode has been reached
+    Finished,
+}
+
+
+/// A complete game which is advanced step by step, without any renderer or ECS
+///
+/// Is added to the world as a resource by the gameplay state and stepped once per frame by the
+/// game system
+pub struct Game {
+    pub board: Board,
+    pub active: Option<ActivePiece>,
+    pub randomizer: Box<dyn Randomizer>,
+    pub hold: HoldSlot,
+    pub input_buffer: InputBuffer,
+    pub score: Score,
+    pub level: Level,
+    pub gravity_curve: GravityCurve,
+    pub gravity_timer: GravityTimer,
+    pub lock_timer: LockTimer,
+    pub phase_timer: PhaseTimer,
+    pub game_over: Option<GameOverReason>,
+}
+
+impl Game {
+    pub fn new(randomizer: Box<dyn Randomizer>, gravity_curve: GravityCurve) -> Game {
+        let level = Level::default();
+        let mut gravity_timer = GravityTimer::default();
+        gravity_timer.set_threshold(gravity_curve.threshold(level.level));
+        Game {
+            board: Board::default(),
+            active: None,
+            randomizer,
+            hold: HoldSlot::default(),
+            input_buffer: InputBuffer::default(),
+            score: Score::default(),
+            level,
+            gravity_curve,
+            gravity_timer,
+            lock_timer: LockTimer::default(),
+            phase_timer: PhaseTimer::default(),
+            game_over: None,
+        }
+    }
+
+    /// Advances the game by dt and returns everything that happened
+    /// inputs: all inputs made since the last step, SoftDrop has to be passed every step for as
+    /// long as it is held
+    pub fn step(&mut self, inputs: &[Input], dt: Duration) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        if self.game_over.is_some() {
+            return events;
+        }
+
+        self.gravity_timer.add_time(dt);
+        self.lock_timer.add_time(dt);
+        self.phase_timer.add_time(dt);
+
+        if self.phase_timer.should_spawn() {
+            self.spawn(&mut events);
+        }
+
+        self.gravity_timer.set_soft_drop(inputs.contains(&Input::SoftDrop));
+        for input in inputs {
+            if self.game_over.is_some() {
+                return events;
+            }
+            self.apply_input(*input, &mut events);
+        }
+        if self.game_over.is_some() {
+            return events;
+        }
+
+        self.apply_gravity(&mut events);
+
+        if self.phase_timer.should_clear() {
+            self.clear(&mut events);
+        }
+
+        events
+    }
+
+    fn apply_input(&mut self, input: Input, events: &mut Vec<GameEvent>) {
+        let piece = match self.active.as_mut() {
+            Some(piece) => piece,
+            None => {
+                self.buffer_input(input);
+                return;
+            },
+        };
+        // NOTE: a shift of 2 is a shift of ONE block in coordinate space
+        let moved = match input {
+            Input::Left => piece.try_move(&self.board, -2, 0),
+            Input::Right => piece.try_move(&self.board, 2, 0),
+            Input::LeftToWall => piece.shift_to_wall(&self.board, -2),
+            Input::RightToWall => piece.shift_to_wall(&self.board, 2),
+            Input::RotateClockwise => piece.try_rotate(&self.board, true),
+            Input::RotateCounterClockwise => piece.try_rotate(&self.board, false),
+            Input::Rotate180 => piece.try_rotate_180(&self.board),
+            // Soft drop only speeds up gravity
+            Input::SoftDrop => false,
+            Input::HardDrop => {
+                let distance = piece.hard_drop(&self.board);
+                self.score.add_hard_drop((distance / 2) as u32);
+                self.lock(events);
+                false
+            },
+            Input::Hold => {
+                self.hold_piece(events);
+                false
+            },
+        };
+        if moved {
+            self.lock_timer.on_move();
+        }
+    }
+
+    /// Keeps rotations and holds made while no piece is in play for the next piece
+    fn buffer_input(&mut self, input: Input) {
+        match input {
+            Input::RotateClockwise => self.input_buffer.rotation = Some(InitialRotation::Clockwise),
+            Input::RotateCounterClockwise => self.input_buffer.rotation = Some(InitialRotation::CounterClockwise),
+            Input::Rotate180 => self.input_buffer.rotation = Some(InitialRotation::Half),
+            Input::Hold => self.input_buffer.hold = true,
+            _ => (),
+        }
+    }
+
+    /// Moves the piece down by one tile for every time the gravity threshold has been reached, for
+    /// as long as there is space below it, and locks it once the lock delay has passed
+    fn apply_gravity(&mut self, events: &mut Vec<GameEvent>) {
+        let piece = match self.active.as_mut() {
+            Some(piece) => piece,
+            None => {
+                self.gravity_timer.reset();
+                return;
+            },
+        };
+
+        // NOTE: -2 is a shift ONE block down in coordinate space
+        let rows = self.gravity_timer.take_rows();
+        let board = &self.board;
+        let fallen = (0..rows).take_while(|_| piece.try_move(board, 0, -2)).count() as u32;
+        if self.gravity_timer.soft_drop {
+            self.score.add_soft_drop(fallen);
+        }
+
+        // The lock delay runs for as long as the piece rests on the stack, and the piece is only
+        // locked if it is still resting there once the delay has passed
+        // It only starts over once the piece has moved further down than ever before
+        self.lock_timer.set_row(piece.lowest_row());
+        let grounded = piece.is_grounded(&self.board);
+        self.phase_timer.set_grounded(grounded);
+        if grounded {
+            self.lock_timer.start();
+            if self.lock_timer.should_lock() {
+                self.lock(events);
+            }
+        } else {
+            self.lock_timer.stop();
+        }
+    }
+
+    /// Draws the next piece once the entry delay has passed, applying the buffered hold and
+    /// rotation to it
+    fn spawn(&mut self, events: &mut Vec<GameEvent>) {
+        let next = self.randomizer.advance();
+        self.hold.reset_use();
+
+        // Initial hold: the next piece is put aside right away
+        let spawned = self.input_buffer.apply_hold(next, &mut self.hold, &mut *self.randomizer);
+        if !self.hold.can_hold() {
+            events.push(GameEvent::Held(next));
+        }
+
+        // Initial rotation: the piece spawns already rotated
+        let mut piece = ActivePiece::spawn(spawned);
+        self.input_buffer.apply_rotation(&mut piece, &self.board);
+        self.input_buffer.clear();
+        self.bring_into_play(piece, events);
+    }
+
+    /// Swaps the piece in play with the held piece, or with the next piece if nothing is being
+    /// held yet
+    /// Hold may only be used once per piece
+    fn hold_piece(&mut self, events: &mut Vec<GameEvent>) {
+        if !self.hold.can_hold() {
+            return;
+        }
+        let current = match self.active.take() {
+            Some(piece) => piece.piece,
+            None => return,
+        };
+        let next = match self.hold.swap(current) {
+            Some(held) => held,
+            None => self.randomizer.advance(),
+        };
+        events.push(GameEvent::Held(current));
+        self.gravity_timer.reset();
+        self.bring_into_play(ActivePiece::spawn(next), events);
+    }
+
+    /// Ends the game if the new piece overlaps the stack
+    fn bring_into_play(&mut self, piece: ActivePiece, events: &mut Vec<GameEvent>) {
+        if !piece.fits(&self.board) {
+            self.end(GameOverReason::BlockOut, events);
+            return;
+        }
+        self.lock_timer.reset();
+        self.phase_timer.enter(Phase::Falling);
+        events.push(GameEvent::Spawned(piece.clone()));
+        self.active = Some(piece);
+    }
+
+    /// Adds the piece to the board and moves on to the line clear phase if rows have been
+    /// completed, otherwise straight to the entry delay of the next piece
+    /// Ends the game if the piece was locked completely above the visible play field
+    fn lock(&mut self, events: &mut Vec<GameEvent>) {
+        let piece = match self.active.take() {
+            Some(piece) => piece,
+            None => return,
+        };
+        let lock_out = self.board.lock(&piece);
+        self.lock_timer.reset();
+        self.phase_timer.lock(!self.board.full_rows().is_empty());
+        events.push(GameEvent::Locked(piece));
+        if let Some(reason) = lock_out {
+            self.end(reason, events);
+        }
+    }
+
+    /// Removes the completed rows once the line clear delay has passed
+    /// Clears are scored at the level they were made at, before the level goes up
+    fn clear(&mut self, events: &mut Vec<GameEvent>) {
+        self.phase_timer.enter(Phase::Entry);
+        let full_rows = self.board.full_rows();
+        self.board.clear_rows(&full_rows);
+        self.score.add_clear(full_rows.len(), self.level.level);
+        if self.level.add_lines(full_rows.len() as u32) {
+            self.gravity_timer.set_threshold(self.gravity_curve.threshold(self.level.level));
+        }
+        events.push(GameEvent::LinesCleared(full_rows));
+    }
+
+    fn end(&mut self, reason: GameOverReason, events: &mut Vec<GameEvent>) {
+        self.game_over = Some(reason);
+        events.push(GameEvent::GameOver(reason));
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{ENTRY_DELAY, LINE_CLEAR_DELAY, LOCK_DELAY};
+
+    // Deals the given pieces over and over again
+    struct Sequence(Vec<Tetrominos>);
+
+    impl Randomizer for Sequence {
+        fn advance(&mut self) -> Tetrominos {
+            let piece = self.0.remove(0);
+            self.0.push(piece);
+            piece
+        }
+
+        fn preview(&mut self, count: usize) -> Vec<Tetrominos> {
+            self.0.iter().cycle().take(count).cloned().collect()
+        }
+    }
+
+    fn game(pieces: &[Tetrominos]) -> Game {
+        Game::new(Box::new(Sequence(pieces.to_vec())), GravityCurve::Guideline)
+    }
+
+    fn no_time() -> Duration {
+        Duration::new(0, 0)
+    }
+
+    #[test]
+    fn first_step_spawns_the_first_piece() {
+        let mut game = game(&[Tetrominos::T, Tetrominos::I]);
+        let events = game.step(&[], no_time());
+        assert_eq!(events, vec![GameEvent::Spawned(ActivePiece::spawn(Tetrominos::T))]);
+        assert_eq!(game.active, Some(ActivePiece::spawn(Tetrominos::T)));
+        assert_eq!(game.phase_timer.phase, Phase::Falling);
+    }
+
+    #[test]
+    fn gravity_moves_the_piece_down_once_the_threshold_is_reached() {
+        let mut game = game(&[Tetrominos::T]);
+        game.step(&[], no_time());
+        game.step(&[], Duration::from_millis(999));
+        assert_eq!(game.active.as_ref().unwrap().lowest_row(), 42);
+        game.step(&[], Duration::from_millis(1));
+        assert_eq!(game.active.as_ref().unwrap().lowest_row(), 40);
+    }
+
+    #[test]
+    fn grounded_piece_is_locked_after_the_lock_delay() {
+        let mut game = game(&[Tetrominos::T]);
+        game.step(&[], no_time());
+        // Soft drop all the way down, one point per cell
+        for _ in 0..21 {
+            game.step(&[Input::SoftDrop], Duration::from_millis(50));
+        }
+        assert_eq!(game.score.points, 21);
+        assert_eq!(game.phase_timer.phase, Phase::Locking);
+
+        assert!(game.step(&[], LOCK_DELAY - Duration::from_millis(1)).is_empty());
+        let locked = game.active.clone().unwrap();
+        assert_eq!(game.step(&[], Duration::from_millis(1)), vec![GameEvent::Locked(locked)]);
+        assert_eq!(game.active, None);
+        assert!(game.board.is_occupied(8, 0));
+        assert_eq!(game.phase_timer.phase, Phase::Entry);
+    }
+
+    #[test]
+    fn hard_drop_locks_the_piece_and_the_next_one_spawns_after_the_entry_delay() {
+        let mut game = game(&[Tetrominos::I, Tetrominos::O]);
+        game.step(&[], no_time());
+        let events = game.step(&[Input::Left, Input::HardDrop], no_time());
+        let mut locked = ActivePiece::spawn(Tetrominos::I);
+        locked.try_move(&Board::default(), -2, -42);
+        assert_eq!(events, vec![GameEvent::Locked(locked)]);
+        assert_eq!(game.score.points, 42);
+        assert!(game.board.is_occupied(4, 0));
+
+        assert!(game.step(&[], ENTRY_DELAY - Duration::from_millis(1)).is_empty());
+        let events = game.step(&[], Duration::from_millis(1));
+        assert_eq!(events, vec![GameEvent::Spawned(ActivePiece::spawn(Tetrominos::O))]);
+    }
+
+    #[test]
+    fn completed_rows_are_cleared_after_the_line_clear_delay() {
+        let mut game = game(&[Tetrominos::I]);
+        // Leave a gap for the I piece in the bottom row
+        for x in (0..20).step_by(2).filter(|x| *x < 6 || *x > 12) {
+            game.board.set_occupied(x, 0, true);
+        }
+        game.board.set_occupied(0, 2, true);
+        game.step(&[], no_time());
+        game.step(&[Input::HardDrop], no_time());
+        assert_eq!(game.phase_timer.phase, Phase::LineClear);
+
+        assert!(game.step(&[], LINE_CLEAR_DELAY - Duration::from_millis(1)).is_empty());
+        let events = game.step(&[], Duration::from_millis(1));
+        assert_eq!(events, vec![GameEvent::LinesCleared(vec![0])]);
+        // The block above the cleared row has moved down
+        assert!(game.board.is_occupied(0, 0));
+        assert!(!game.board.is_occupied(2, 0));
+        assert_eq!(game.score.points, 42 + 100);
+        assert_eq!(game.level.lines, 1);
+        assert_eq!(game.phase_timer.phase, Phase::Entry);
+    }
+
+    #[test]
+    fn hold_swaps_the_piece_once_per_piece() {
+        let mut game = game(&[Tetrominos::T, Tetrominos::I, Tetrominos::O]);
+        game.step(&[], no_time());
+        let events = game.step(&[Input::Hold], no_time());
+        assert_eq!(events, vec![
+            GameEvent::Held(Tetrominos::T),
+            GameEvent::Spawned(ActivePiece::spawn(Tetrominos::I)),
+        ]);
+        assert_eq!(game.hold.piece, Some(Tetrominos::T));
+        assert!(game.step(&[Input::Hold], no_time()).is_empty());
+
+        // The held piece comes back once the next piece has spawned
+        game.step(&[Input::HardDrop], no_time());
+        game.step(&[], ENTRY_DELAY);
+        let events = game.step(&[Input::Hold], no_time());
+        assert_eq!(events, vec![
+            GameEvent::Held(Tetrominos::O),
+            GameEvent::Spawned(ActivePiece::spawn(Tetrominos::T)),
+        ]);
+    }
+
+    #[test]
+    fn inputs_during_the_entry_delay_apply_to_the_next_piece() {
+        let mut game = game(&[Tetrominos::T, Tetrominos::I, Tetrominos::J]);
+        game.step(&[], no_time());
+        game.step(&[Input::HardDrop], no_time());
+        assert!(game.step(&[Input::Hold, Input::RotateClockwise, Input::Left], no_time()).is_empty());
+
+        let events = game.step(&[], ENTRY_DELAY);
+        let mut rotated = ActivePiece::spawn(Tetrominos::J);
+        rotated.try_rotate(&game.board, true);
+        assert_eq!(events, vec![GameEvent::Held(Tetrominos::I), GameEvent::Spawned(rotated)]);
+        assert_eq!(game.hold.piece, Some(Tetrominos::I));
+        assert!(!game.hold.can_hold());
+    }
+
+    #[test]
+    fn piece_overlapping_the_stack_on_spawn_ends_the_game() {
+        let mut game = game(&[Tetrominos::T]);
+        game.board.set_occupied(8, 42, true);
+        let events = game.step(&[], no_time());
+        assert_eq!(events, vec![GameEvent::GameOver(GameOverReason::BlockOut)]);
+        assert_eq!(game.game_over, Some(GameOverReason::BlockOut));
+        assert!(game.step(&[], ENTRY_DELAY).is_empty());
+    }
+}
@@ -0,0 +1,341 @@
+use std::time::Duration;
+
+use crate::constants::ARENA_WIDTH;
+use crate::tetris_core::board::Board;
+use crate::tetris_core::hold::HoldSlot;
+use crate::tetris_core::piece::{ActivePiece, Tetrominos};
+use crate::tetris_core::randomizer::Randomizer;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+/// A sideways movement of the falling piece caused by the player
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Shift {
+    // Move by one block
+    Step(Direction),
+    // Move as far as possible, used for an ARR of 0
+    ToWall(Direction),
+}
+
+
+/// Turns the held left and right inputs into sideways movements
+///
+/// The most recently pressed direction wins while both are held. Once a direction has been held
+/// for the DAS it is repeated every ARR. After a rotation or spawn the repetition can be paused
+/// for the DAS cut, so that a charged DAS does not immediately throw the new piece to the wall.
+pub struct AutoShift {
+    pub das: Duration,
+    pub arr: Duration,
+    pub das_cut: Duration,
+    // The held directions, the most recently pressed one last
+    held: Vec<Direction>,
+    // How long the active direction has been held
+    held_time: Duration,
+    // Whether the active direction is already being repeated
+    repeating: bool,
+    // Time since the last repeated movement
+    repeat_time: Duration,
+    // Time left until the movement may be repeated again after a DAS cut
+    cut_time: Duration,
+}
+
+impl AutoShift {
+    pub fn new(das: Duration, arr: Duration, das_cut: Duration) -> AutoShift {
+        AutoShift {
+            das,
+            arr,
+            das_cut,
+            held: Vec::new(),
+            held_time: Duration::new(0, 0),
+            repeating: false,
+            repeat_time: Duration::new(0, 0),
+            cut_time: Duration::new(0, 0),
+        }
+    }
+
+    /// Makes the direction the active one and returns the movement of the initial key press
+    pub fn press(&mut self, direction: Direction) -> Shift {
+        self.held.retain(|held| *held != direction);
+        self.held.push(direction);
+        self.restart();
+        Shift::Step(direction)
+    }
+
+    /// Stops repeating the direction, the other direction takes over if it is still held
+    pub fn release(&mut self, direction: Direction) {
+        if self.active() == Some(direction) {
+            self.restart();
+        }
+        self.held.retain(|held| *held != direction);
+    }
+
+    /// Returns the direction that is currently repeated
+    pub fn active(&self) -> Option<Direction> {
+        self.held.last().cloned()
+    }
+
+    /// Pauses the repetition for the DAS cut, should be called after every rotation and spawn
+    /// The repetition starts over once the cut is over, so the piece moves right away
+    pub fn cut(&mut self) {
+        if self.das_cut > Duration::new(0, 0) {
+            self.cut_time = self.das_cut;
+            self.repeating = false;
+        }
+    }
+
+    /// Advances the timers by dt and returns all movements that are due in this frame
+    pub fn update(&mut self, dt: Duration) -> Vec<Shift> {
+        let mut shifts = Vec::new();
+        // The part of this frame during which the movement is still paused
+        let cut_left = self.cut_time.min(dt);
+        let cutting = self.cut_time > dt;
+        self.cut_time -= cut_left;
+
+        let direction = match self.active() {
+            Some(direction) => direction,
+            None => return shifts,
+        };
+
+        // The DAS keeps charging during a DAS cut, only the movement itself is delayed
+        self.held_time += dt;
+        if self.held_time < self.das || cutting {
+            return shifts;
+        }
+
+        if self.arr == Duration::new(0, 0) {
+            shifts.push(Shift::ToWall(direction));
+            return shifts;
+        }
+
+        // Only the part of the frame after both the DAS and the DAS cut counts towards the
+        // repetitions, the first one happens as soon as it is allowed, the following ones every ARR
+        let allowed = (self.held_time - self.das).min(dt - cut_left);
+        if self.repeating {
+            self.repeat_time += allowed;
+        } else {
+            self.repeating = true;
+            self.repeat_time = self.arr + allowed;
+        }
+        // No piece can move further than the width of the arena
+        let max_steps = (ARENA_WIDTH / 2) as usize;
+        while self.repeat_time >= self.arr && shifts.len() < max_steps {
+            self.repeat_time -= self.arr;
+            shifts.push(Shift::Step(direction));
+        }
+        if shifts.len() == max_steps {
+            self.repeat_time = Duration::new(0, 0);
+        }
+        shifts
+    }
+
+    fn restart(&mut self) {
+        self.held_time = Duration::new(0, 0);
+        self.repeat_time = Duration::new(0, 0);
+        self.repeating = false;
+    }
+}
+
+
+/// A rotation which was made while no piece was in play
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum InitialRotation {
+    Clockwise,
+    CounterClockwise,
+    Half,
+}
+
+/// Keeps the rotation and hold inputs made while no piece is in play, so that they can be
+/// applied to the next piece as it spawns (IRS and IHS)
+/// Only the most recent rotation is kept
+pub struct InputBuffer {
+    pub rotation: Option<InitialRotation>,
+    pub hold: bool,
+}
+
+impl InputBuffer {
+    /// Rotates a newly spawned piece by the buffered rotation
+    /// The piece stays in its spawn orientation if the rotation is not possible
+    pub fn apply_rotation(&self, piece: &mut ActivePiece, board: &Board) {
+        match self.rotation {
+            Some(InitialRotation::Clockwise) => { piece.try_rotate(board, true); },
+            Some(InitialRotation::CounterClockwise) => { piece.try_rotate(board, false); },
+            Some(InitialRotation::Half) => { piece.try_rotate_180(board); },
+            None => (),
+        }
+    }
+
+    /// Puts the piece which is about to spawn into the hold slot if a hold was buffered
+    /// Returns the piece which spawns instead
+    pub fn apply_hold(&self, next: Tetrominos, hold: &mut HoldSlot, randomizer: &mut dyn Randomizer) -> Tetrominos {
+        if !self.hold || !hold.can_hold() {
+            return next;
+        }
+        match hold.swap(next) {
+            Some(held) => held,
+            None => randomizer.advance(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.rotation = None;
+        self.hold = false;
+    }
+}
+
+impl Default for InputBuffer {
+    fn default() -> Self {
+        InputBuffer { rotation: None, hold: false }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tetris_core::randomizer::BagRandomizer;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn steps(direction: Direction, count: usize) -> Vec<Shift> {
+        vec![Shift::Step(direction); count]
+    }
+
+    #[test]
+    fn last_pressed_direction_wins() {
+        let mut auto_shift = AutoShift::new(ms(100), ms(20), ms(0));
+        assert_eq!(auto_shift.press(Direction::Left), Shift::Step(Direction::Left));
+        assert_eq!(auto_shift.press(Direction::Right), Shift::Step(Direction::Right));
+        assert_eq!(auto_shift.active(), Some(Direction::Right));
+
+        // Releasing the inactive direction changes nothing
+        auto_shift.update(ms(90));
+        auto_shift.release(Direction::Left);
+        assert_eq!(auto_shift.update(ms(10)), steps(Direction::Right, 1));
+
+        // Releasing the active direction hands over to the other one, which has to charge again
+        auto_shift.press(Direction::Left);
+        auto_shift.release(Direction::Left);
+        assert_eq!(auto_shift.active(), Some(Direction::Right));
+        assert!(auto_shift.update(ms(99)).is_empty());
+        assert_eq!(auto_shift.update(ms(1)), steps(Direction::Right, 1));
+
+        auto_shift.release(Direction::Right);
+        assert_eq!(auto_shift.active(), None);
+        assert!(auto_shift.update(ms(1000)).is_empty());
+    }
+
+    #[test]
+    fn first_repeat_happens_after_the_das_then_every_arr() {
+        let mut auto_shift = AutoShift::new(ms(100), ms(20), ms(0));
+        auto_shift.press(Direction::Left);
+        assert!(auto_shift.update(ms(50)).is_empty());
+        // 10 ms past the DAS count towards the next repetition
+        assert_eq!(auto_shift.update(ms(60)), steps(Direction::Left, 1));
+        assert!(auto_shift.update(ms(9)).is_empty());
+        assert_eq!(auto_shift.update(ms(1)), steps(Direction::Left, 1));
+        assert_eq!(auto_shift.update(ms(40)), steps(Direction::Left, 2));
+    }
+
+    #[test]
+    fn zero_arr_shifts_to_the_wall() {
+        let mut auto_shift = AutoShift::new(ms(100), ms(0), ms(0));
+        auto_shift.press(Direction::Right);
+        assert!(auto_shift.update(ms(99)).is_empty());
+        assert_eq!(auto_shift.update(ms(1)), vec![Shift::ToWall(Direction::Right)]);
+    }
+
+    #[test]
+    fn das_cut_delays_the_movement_but_keeps_charging() {
+        let mut auto_shift = AutoShift::new(ms(100), ms(20), ms(150));
+        auto_shift.press(Direction::Right);
+        auto_shift.cut();
+        assert!(auto_shift.update(ms(100)).is_empty());
+        // The DAS has been charged during the cut, so the piece moves as soon as it is over
+        assert_eq!(auto_shift.update(ms(60)), steps(Direction::Right, 1));
+
+        auto_shift.cut();
+        assert!(auto_shift.update(ms(149)).is_empty());
+        assert!(!auto_shift.update(ms(1)).is_empty());
+    }
+
+    #[test]
+    fn large_steps_catch_up_to_the_width_of_the_arena() {
+        let mut auto_shift = AutoShift::new(ms(100), ms(20), ms(0));
+        auto_shift.press(Direction::Left);
+        assert_eq!(auto_shift.update(ms(160)), steps(Direction::Left, 4));
+
+        let max_steps = (ARENA_WIDTH / 2) as usize;
+        assert_eq!(auto_shift.update(ms(10_000)), steps(Direction::Left, max_steps));
+        // The time past the cap is dropped instead of being carried over
+        assert!(auto_shift.update(ms(19)).is_empty());
+        assert_eq!(auto_shift.update(ms(1)), steps(Direction::Left, 1));
+    }
+
+    #[test]
+    fn buffered_rotation_is_applied_to_the_new_piece() {
+        let board = Board::default();
+        let mut buffer = InputBuffer { rotation: Some(InitialRotation::CounterClockwise), hold: false };
+        let mut piece = ActivePiece::spawn(Tetrominos::T);
+        buffer.apply_rotation(&mut piece, &board);
+        assert_eq!(piece.rotation, 3);
+
+        buffer.rotation = Some(InitialRotation::Half);
+        let mut piece = ActivePiece::spawn(Tetrominos::J);
+        buffer.apply_rotation(&mut piece, &board);
+        assert_eq!(piece.rotation, 2);
+
+        buffer.hold = true;
+        buffer.clear();
+        assert_eq!(buffer.rotation, None);
+        assert!(!buffer.hold);
+        let mut piece = ActivePiece::spawn(Tetrominos::T);
+        buffer.apply_rotation(&mut piece, &board);
+        assert_eq!(piece.rotation, 0);
+    }
+
+    #[test]
+    fn buffered_hold_takes_the_piece_after_next_into_an_empty_slot() {
+        let mut randomizer = BagRandomizer::new(1);
+        let upcoming = randomizer.preview(2);
+        let mut hold = HoldSlot::default();
+        let buffer = InputBuffer { rotation: None, hold: true };
+
+        let next = randomizer.advance();
+        assert_eq!(buffer.apply_hold(next, &mut hold, &mut randomizer), upcoming[1]);
+        assert_eq!(hold.piece, Some(upcoming[0]));
+        assert!(!hold.can_hold());
+    }
+
+    #[test]
+    fn buffered_hold_swaps_with_the_held_piece() {
+        let mut randomizer = BagRandomizer::new(1);
+        let mut hold = HoldSlot::default();
+        hold.swap(Tetrominos::I);
+        hold.reset_use();
+        let buffer = InputBuffer { rotation: None, hold: true };
+
+        let next = randomizer.advance();
+        assert_eq!(buffer.apply_hold(next, &mut hold, &mut randomizer), Tetrominos::I);
+        assert_eq!(hold.piece, Some(next));
+
+        // Hold may only be used once per piece
+        let next = randomizer.advance();
+        assert_eq!(buffer.apply_hold(next, &mut hold, &mut randomizer), next);
+    }
+
+    #[test]
+    fn nothing_is_held_without_a_buffered_hold() {
+        let mut randomizer = BagRandomizer::new(1);
+        let mut hold = HoldSlot::default();
+        let buffer = InputBuffer::default();
+        let next = randomizer.advance();
+        assert_eq!(buffer.apply_hold(next, &mut hold, &mut randomizer), next);
+        assert_eq!(hold.piece, None);
+    }
+}
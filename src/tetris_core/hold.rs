@@ -0,0 +1,32 @@
+use crate::tetris_core::piece::Tetrominos;
+
+/// Keeps track of the piece the player has put aside
+/// used: whether hold has already been used for the current piece, which is only allowed once
+pub struct HoldSlot {
+    pub piece: Option<Tetrominos>,
+    pub used: bool,
+}
+
+impl HoldSlot {
+    pub fn can_hold(&self) -> bool {
+        !self.used
+    }
+
+    /// Puts the current piece into the slot and returns the previously held piece
+    /// Returns None if the slot was empty, in which case the next piece should be taken instead
+    pub fn swap(&mut self, current: Tetrominos) -> Option<Tetrominos> {
+        self.used = true;
+        self.piece.replace(current)
+    }
+
+    /// Allows hold to be used again, should be called whenever a new piece is spawned
+    pub fn reset_use(&mut self) {
+        self.used = false;
+    }
+}
+
+impl Default for HoldSlot {
+    fn default() -> Self {
+        HoldSlot { piece: None, used: false }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use crate::constants::LINES_PER_LEVEL;
+
+/// Keeps track of the current level, which goes up by one every LINES_PER_LEVEL cleared lines
+pub struct Level {
+    pub level: u32,
+    pub lines: u32,
+}
+
+impl Level {
+    /// Counts the cleared lines and returns whether the level went up
+    pub fn add_lines(&mut self, lines: u32) -> bool {
+        let previous = self.level;
+        self.lines += lines;
+        self.level = 1 + self.lines / LINES_PER_LEVEL;
+        self.level != previous
+    }
+}
+
+impl Default for Level {
+    fn default() -> Self {
+        Level { level: 1, lines: 0 }
+    }
+}
+
+
+/// Determines how long it takes a piece to fall by one row at every level
+pub enum GravityCurve {
+    // (0.8 - (level - 1) * 0.007) ^ (level - 1) seconds per row
+    Guideline,
+    // The time per row for every level, starting at level 1
+    // Levels past the end of the table keep the last entry
+    Table(Vec<Duration>),
+}
+
+impl GravityCurve {
+    pub fn threshold(&self, level: u32) -> Duration {
+        match self {
+            GravityCurve::Guideline => {
+                let n = level.max(1) as f64 - 1.;
+                let seconds = (0.8 - n * 0.007).powf(n);
+                Duration::from_nanos((seconds * 1e9) as u64)
+            },
+            GravityCurve::Table(thresholds) => {
+                let index = (level.max(1) as usize - 1).min(thresholds.len() - 1);
+                thresholds[index]
+            },
+        }
+    }
+}
+
+impl Default for GravityCurve {
+    fn default() -> Self {
+        GravityCurve::Guideline
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_goes_up_every_ten_lines() {
+        let mut level = Level::default();
+        assert!(!level.add_lines(LINES_PER_LEVEL - 1));
+        assert_eq!(level.level, 1);
+        assert!(level.add_lines(1));
+        assert_eq!(level.level, 2);
+        assert!(!level.add_lines(3));
+        assert_eq!(level.lines, LINES_PER_LEVEL + 3);
+    }
+
+    #[test]
+    fn several_levels_can_be_gained_at_once() {
+        let mut level = Level::default();
+        assert!(level.add_lines(LINES_PER_LEVEL * 2 + 1));
+        assert_eq!(level.level, 3);
+    }
+
+    #[test]
+    fn guideline_curve_gets_faster_with_every_level() {
+        let curve = GravityCurve::Guideline;
+        assert_eq!(curve.threshold(1), Duration::from_secs(1));
+        assert_eq!(curve.threshold(0), curve.threshold(1));
+        // (0.8 - 0.007) ^ 1 seconds
+        assert_eq!(curve.threshold(2).as_micros(), 793_000);
+        for level in 1..20 {
+            assert!(curve.threshold(level + 1) < curve.threshold(level));
+        }
+    }
+
+    #[test]
+    fn table_curve_keeps_its_last_entry() {
+        let curve = GravityCurve::Table(vec![Duration::from_millis(500), Duration::from_millis(200)]);
+        assert_eq!(curve.threshold(1), Duration::from_millis(500));
+        assert_eq!(curve.threshold(2), Duration::from_millis(200));
+        assert_eq!(curve.threshold(30), Duration::from_millis(200));
+    }
+}
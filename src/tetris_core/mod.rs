@@ -0,0 +1,21 @@
+//! Engine independent implementation of the game rules
+//!
+//! Nothing in here depends on amethyst, so the rules can be used and tested without opening a
+//! window. `game::Game` owns the state of a game and advances it with `Game::step`. The game
+//! system turns the player's key presses into inputs for it, and the other systems in
+//! `crate::systems` bring the events it returns into the ECS and display its state.
+//!
+//! All coordinates use the same space as the `Block` component: every cell spans two units in
+//! each direction, which allows rotation centers to lie between two cells.
+pub mod board;
+pub mod game;
+pub mod handling;
+pub mod hold;
+pub mod level;
+pub mod mode;
+pub mod phase;
+pub mod piece;
+pub mod randomizer;
+pub mod rotation;
+pub mod score;
+pub mod timers;
\ No newline at end of file
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use crate::constants::{SPRINT_LINES, ULTRA_TIME};
+
+/// The different goals a game can be played with
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GameMode {
+    // Endless, the game only ends once the player tops out
+    Marathon,
+    // Clear SPRINT_LINES lines as fast as possible
+    Sprint,
+    // Score as many points as possible within ULTRA_TIME
+    Ultra,
+}
+
+impl GameMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Marathon => "Marathon",
+            GameMode::Sprint => "Sprint",
+            GameMode::Ultra => "Ultra",
+        }
+    }
+
+    /// Returns whether the goal of the mode has been reached
+    pub fn is_complete(&self, lines: u32, time: Duration) -> bool {
+        match self {
+            GameMode::Marathon => false,
+            GameMode::Sprint => lines >= SPRINT_LINES,
+            GameMode::Ultra => time >= ULTRA_TIME,
+        }
+    }
+}
+
+impl Default for GameMode {
+    fn default() -> Self {
+        GameMode::Marathon
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use crate::constants::{ENTRY_DELAY, LINE_CLEAR_DELAY};
+
+/// The phases every piece goes through, in this order
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Phase {
+    // The piece is falling freely
+    Falling,
+    // The piece rests on the stack and the lock delay is running
+    Locking,
+    // The piece has been locked and completed rows, which are removed once the delay has passed
+    LineClear,
+    // Waiting for the next piece to appear (ARE), which is spawned once the delay has passed
+    Entry,
+}
+
+/// Keeps track of the current phase and of how long the game has been in it
+///
+/// Is advanced with every step of the game, which switches to the next phase once the piece has
+/// been locked, the rows have been cleared or the next piece has spawned
+pub struct PhaseTimer {
+    pub phase: Phase,
+    pub timer: Duration,
+    pub line_clear_delay: Duration,
+    pub entry_delay: Duration,
+}
+
+impl PhaseTimer {
+    /// Starts the game in the entry phase with the delay already passed, so that the first piece
+    /// spawns immediately
+    pub fn new(line_clear_delay: Duration, entry_delay: Duration) -> PhaseTimer {
+        PhaseTimer { phase: Phase::Entry, timer: entry_delay, line_clear_delay, entry_delay }
+    }
+
+    pub fn add_time(&mut self, time: Duration) {
+        self.timer += time;
+    }
+
+    pub fn enter(&mut self, phase: Phase) {
+        self.phase = phase;
+        self.timer = Duration::new(0, 0);
+    }
+
+    /// Switches between falling and locking without restarting the timer of the phase
+    pub fn set_grounded(&mut self, grounded: bool) {
+        match (self.phase, grounded) {
+            (Phase::Falling, true) => self.phase = Phase::Locking,
+            (Phase::Locking, false) => self.phase = Phase::Falling,
+            _ => (),
+        }
+    }
+
+    /// Should be called once a piece has been locked
+    /// Completed rows are kept on the board for the line clear delay, otherwise the entry delay
+    /// starts right away
+    pub fn lock(&mut self, rows_completed: bool) {
+        self.enter(if rows_completed { Phase::LineClear } else { Phase::Entry });
+    }
+
+    pub fn should_clear(&self) -> bool {
+        self.phase == Phase::LineClear && self.timer >= self.line_clear_delay
+    }
+
+    pub fn should_spawn(&self) -> bool {
+        self.phase == Phase::Entry && self.timer >= self.entry_delay
+    }
+
+    /// Returns whether a piece is currently in play
+    pub fn has_piece(&self) -> bool {
+        self.phase == Phase::Falling || self.phase == Phase::Locking
+    }
+}
+
+impl Default for PhaseTimer {
+    fn default() -> Self {
+        PhaseTimer::new(LINE_CLEAR_DELAY, ENTRY_DELAY)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn first_piece_spawns_immediately() {
+        let timer = PhaseTimer::new(ms(300), ms(100));
+        assert_eq!(timer.phase, Phase::Entry);
+        assert!(timer.should_spawn());
+        assert!(!timer.has_piece());
+    }
+
+    #[test]
+    fn grounding_switches_between_falling_and_locking() {
+        let mut timer = PhaseTimer::new(ms(300), ms(100));
+        timer.enter(Phase::Falling);
+        timer.add_time(ms(50));
+        timer.set_grounded(true);
+        assert_eq!(timer.phase, Phase::Locking);
+        assert!(timer.has_piece());
+        timer.set_grounded(false);
+        assert_eq!(timer.phase, Phase::Falling);
+        // Switching does not restart the timer of the phase
+        assert_eq!(timer.timer, ms(50));
+    }
+
+    #[test]
+    fn grounding_is_ignored_without_a_piece() {
+        let mut timer = PhaseTimer::new(ms(300), ms(100));
+        timer.lock(true);
+        timer.set_grounded(false);
+        assert_eq!(timer.phase, Phase::LineClear);
+        timer.enter(Phase::Entry);
+        timer.set_grounded(true);
+        assert_eq!(timer.phase, Phase::Entry);
+    }
+
+    #[test]
+    fn completed_rows_are_cleared_after_the_line_clear_delay() {
+        let mut timer = PhaseTimer::new(ms(300), ms(100));
+        timer.enter(Phase::Locking);
+        timer.lock(true);
+        assert_eq!(timer.phase, Phase::LineClear);
+        timer.add_time(ms(299));
+        assert!(!timer.should_clear());
+        assert!(!timer.should_spawn());
+        timer.add_time(ms(1));
+        assert!(timer.should_clear());
+    }
+
+    #[test]
+    fn next_piece_spawns_after_the_entry_delay() {
+        let mut timer = PhaseTimer::new(ms(300), ms(100));
+        timer.enter(Phase::Locking);
+        timer.lock(false);
+        assert_eq!(timer.phase, Phase::Entry);
+        assert!(!timer.has_piece());
+        timer.add_time(ms(99));
+        assert!(!timer.should_spawn());
+        assert!(!timer.should_clear());
+        timer.add_time(ms(1));
+        assert!(timer.should_spawn());
+    }
+}
@@ -0,0 +1,203 @@
+use crate::constants::SPAWN_POINT;
+use crate::tetris_core::board::Board;
+use crate::tetris_core::rotation;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Tetrominos {
+    I,
+    J,
+    L,
+    O,
+    S,
+    T,
+    Z,
+}
+
+impl Tetrominos {
+    pub fn num_to_tetromino(num: u8) -> Tetrominos {
+        use Tetrominos::{I, J, L, O, S, T, Z};
+        match num {
+            0 => I,
+            1 => J,
+            2 => L,
+            3 => O,
+            4 => S,
+            5 => T,
+            6 => Z,
+            _ => panic!("Invalid index for a Tetromino (maximum is 6, given was {}", num),
+        }
+    }
+}
+
+
+/// Returns a vector describing the positions of all the tetrominoe's blocks
+/// The position is relative to be the spawning block ((5, 21) in the standard case)
+pub fn get_layout(piece: &Tetrominos) -> Vec<(i32, i32)> {
+    match piece {
+        Tetrominos::I => vec![(-1, 0), (0, 0), (1, 0), (2, 0)],
+        Tetrominos::J => vec![(-1, 1), (-1, 0), (0, 0), (1, 0)],
+        Tetrominos::L => vec![(-1, 0), (0, 0), (1, 0), (1, 1)],
+        Tetrominos::O => vec![(0, 0), (0, 1), (1, 0), (1, 1)],
+        Tetrominos::S => vec![(-1, 0), (0, 0), (0, 1), (1, 1)],
+        Tetrominos::T => vec![(-1, 0), (0, 1), (0, 0), (1, 0)],
+        Tetrominos::Z => vec![(-1, 1), (0, 1), (0, 0), (1, 0)],
+    }
+}
+
+/// Returns the correct rotation center relative to the Spawn point
+pub fn get_rotation_center(piece: &Tetrominos) -> (i32, i32) {
+    match piece {
+        Tetrominos::I => (1, -1),
+        Tetrominos::O => (1, 1),
+        _ => (0, 0),
+    }
+}
+
+
+/// The piece which is currently falling
+/// blocks: coordinates of the piece's blocks
+/// center: coordinates of the point the piece rotates around
+/// rotation: the current rotation state, 0 being the spawn orientation
+#[derive(Clone, PartialEq, Debug)]
+pub struct ActivePiece {
+    pub piece: Tetrominos,
+    pub blocks: Vec<(i32, i32)>,
+    pub center: (i32, i32),
+    pub rotation: i32,
+}
+
+impl ActivePiece {
+    /// Creates the given piece in its spawn orientation at the spawn point
+    pub fn spawn(piece: Tetrominos) -> ActivePiece {
+        let center_offset = get_rotation_center(&piece);
+        ActivePiece {
+            piece,
+            blocks: get_layout(&piece)
+                .iter()
+                .map(|(x_offset, y_offset)| (x_offset * 2 + SPAWN_POINT.0, y_offset * 2 + SPAWN_POINT.1))
+                .collect(),
+            center: (SPAWN_POINT.0 + center_offset.0, SPAWN_POINT.1 + center_offset.1),
+            rotation: 0,
+        }
+    }
+
+    /// Returns whether none of the piece's blocks overlap a wall, the floor or a locked block
+    pub fn fits(&self, board: &Board) -> bool {
+        self.blocks.iter().all(|(x, y)| board.is_free(*x, *y))
+    }
+
+    /// Moves the piece by the given amount if the new position is free
+    /// Returns whether the piece was moved
+    pub fn try_move(&mut self, board: &Board, dx: i32, dy: i32) -> bool {
+        let allowed = self.blocks.iter().all(|(x, y)| board.is_free(x + dx, y + dy));
+        if allowed {
+            for block in self.blocks.iter_mut() {
+                block.0 += dx;
+                block.1 += dy;
+            }
+            self.center.0 += dx;
+            self.center.1 += dy;
+        }
+        allowed
+    }
+
+    /// Moves the piece sideways by dx for as long as the new position is free
+    /// Returns whether the piece was moved
+    pub fn shift_to_wall(&mut self, board: &Board, dx: i32) -> bool {
+        let mut moved = false;
+        while self.try_move(board, dx, 0) {
+            moved = true;
+        }
+        moved
+    }
+
+    /// Rotates the piece, applying wall kicks where necessary
+    /// Returns whether the piece was rotated
+    pub fn try_rotate(&mut self, board: &Board, dir_clockwise: bool) -> bool {
+        rotation::rotate(self, board, dir_clockwise)
+    }
+
+    /// Rotates the piece by 180 deg, applying wall kicks where necessary
+    /// Returns whether the piece was rotated
+    pub fn try_rotate_180(&mut self, board: &Board) -> bool {
+        rotation::rotate_180(self, board)
+    }
+
+    /// Returns how far the piece can be moved down before it lands
+    pub fn drop_distance(&self, board: &Board) -> i32 {
+        let mut distance = 0;
+        // NOTE: a distance of 2 is a shift of ONE block in coordinate space
+        while self.blocks.iter().all(|(x, y)| board.is_free(*x, *y - distance - 2)) {
+            distance += 2;
+        }
+        distance
+    }
+
+    /// Returns the y coordinate of the piece's lowest block
+    pub fn lowest_row(&self) -> i32 {
+        self.blocks.iter().map(|(_, y)| *y).min().unwrap_or(0)
+    }
+
+    /// Returns whether the piece is resting on the floor or the stack
+    pub fn is_grounded(&self, board: &Board) -> bool {
+        self.blocks.iter().any(|(x, y)| !board.is_free(*x, *y - 2))
+    }
+
+    /// Moves the piece down as far as possible and returns the distance it was moved by
+    pub fn hard_drop(&mut self, board: &Board) -> i32 {
+        let distance = self.drop_distance(board);
+        self.try_move(board, 0, -distance);
+        distance
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pieces_spawn_at_the_spawn_point() {
+        let piece = ActivePiece::spawn(Tetrominos::I);
+        assert_eq!(piece.blocks, vec![(6, 42), (8, 42), (10, 42), (12, 42)]);
+        assert_eq!(piece.center, (9, 41));
+        assert_eq!(piece.rotation, 0);
+    }
+
+    #[test]
+    fn moves_into_walls_or_blocks_are_rejected() {
+        let mut board = Board::default();
+        board.set_occupied(14, 42, true);
+        let mut piece = ActivePiece::spawn(Tetrominos::I);
+        assert!(!piece.try_move(&board, 2, 0));
+        assert_eq!(piece.blocks[0], (6, 42));
+        assert!(piece.try_move(&board, -2, 0));
+        assert_eq!(piece.blocks[0], (4, 42));
+        assert_eq!(piece.center, (7, 41));
+    }
+
+    #[test]
+    fn shift_to_wall_stops_at_the_wall() {
+        let board = Board::default();
+        let mut piece = ActivePiece::spawn(Tetrominos::I);
+        assert!(piece.shift_to_wall(&board, -2));
+        assert_eq!(piece.blocks, vec![(0, 42), (2, 42), (4, 42), (6, 42)]);
+        assert!(!piece.shift_to_wall(&board, -2));
+    }
+
+    #[test]
+    fn hard_drop_lands_on_the_floor_or_the_stack() {
+        let mut board = Board::default();
+        let mut piece = ActivePiece::spawn(Tetrominos::I);
+        assert!(!piece.is_grounded(&board));
+        assert_eq!(piece.hard_drop(&board), 42);
+        assert!(piece.is_grounded(&board));
+        assert!(piece.blocks.iter().all(|(_, y)| *y == 0));
+
+        board.set_occupied(8, 10, true);
+        let mut piece = ActivePiece::spawn(Tetrominos::I);
+        assert_eq!(piece.drop_distance(&board), 30);
+        piece.hard_drop(&board);
+        assert!(piece.blocks.iter().all(|(_, y)| *y == 12));
+    }
+}
@@ -0,0 +1,165 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+use rand_pcg::Pcg32;
+
+use crate::tetris_core::piece::Tetrominos;
+
+// Stream of the PCG generator, the state is initialized from the seed
+// NOTE: a named generator is used instead of StdRng, whose algorithm may change between versions
+// of rand, so that a seed always results in the same sequence of pieces
+const PCG_STREAM: u64 = 0xda3e_39cb_94b9_5bdb;
+
+/// Creates the random number generator of a randomizer
+fn create_rng(seed: u64) -> Pcg32 {
+    Pcg32::new(seed, PCG_STREAM)
+}
+
+/// Generates the sequence of pieces that will be spawned
+pub trait Randomizer: Send + Sync {
+    /// Returns the next piece and advances the sequence
+    fn advance(&mut self) -> Tetrominos;
+
+    /// Returns the next count pieces without advancing the sequence
+    /// The upcoming pieces are generated as they are needed, so the sequence is the same no matter
+    /// how many of them are previewed
+    fn preview(&mut self, count: usize) -> Vec<Tetrominos>;
+}
+
+
+/// Keeps track of the upcoming pieces and generates the next pieces as they are needed
+/// Never generates the same piece twice in a row
+pub struct RandomStream {
+    next_nums: Vec<u8>,
+    // The most recently generated piece, which the next one has to differ from
+    last: Option<u8>,
+    rng: Pcg32,
+}
+
+impl RandomStream {
+    /// Creates a stream whose sequence of pieces is completely determined by the seed
+    pub fn new(seed: u64) -> RandomStream {
+        RandomStream { next_nums: Vec::new(), last: None, rng: create_rng(seed) }
+    }
+
+    /// Generates pieces until at least count of them are upcoming
+    fn fill(&mut self, count: usize) {
+        while self.next_nums.len() < count {
+            let mut num: u8 = self.rng.gen_range(0, 7);
+            while Some(num) == self.last {
+                num = self.rng.gen_range(0, 7);
+            }
+            self.next_nums.push(num);
+            self.last = Some(num);
+        }
+    }
+}
+
+impl Randomizer for RandomStream {
+    fn advance(&mut self) -> Tetrominos {
+        self.fill(1);
+        Tetrominos::num_to_tetromino(self.next_nums.remove(0))
+    }
+
+    fn preview(&mut self, count: usize) -> Vec<Tetrominos> {
+        self.fill(count);
+        self.next_nums.iter().take(count).map(|num| Tetrominos::num_to_tetromino(*num)).collect()
+    }
+}
+
+impl Default for RandomStream {
+    fn default() -> Self {
+        RandomStream::new(rand::random())
+    }
+}
+
+
+/// Generates the pieces in bags of seven, where every bag contains each tetromino exactly once
+/// in random order
+pub struct BagRandomizer {
+    next_nums: Vec<u8>,
+    rng: Pcg32,
+}
+
+impl BagRandomizer {
+    /// Creates a randomizer whose sequence of pieces is completely determined by the seed
+    pub fn new(seed: u64) -> BagRandomizer {
+        BagRandomizer { next_nums: Vec::new(), rng: create_rng(seed) }
+    }
+
+    /// Adds bags until at least count pieces are upcoming
+    fn fill(&mut self, count: usize) {
+        while self.next_nums.len() < count {
+            let mut bag: Vec<u8> = (0..7).collect();
+            bag.shuffle(&mut self.rng);
+            self.next_nums.extend(bag);
+        }
+    }
+}
+
+impl Randomizer for BagRandomizer {
+    fn advance(&mut self) -> Tetrominos {
+        self.fill(1);
+        Tetrominos::num_to_tetromino(self.next_nums.remove(0))
+    }
+
+    fn preview(&mut self, count: usize) -> Vec<Tetrominos> {
+        self.fill(count);
+        self.next_nums.iter().take(count).map(|num| Tetrominos::num_to_tetromino(*num)).collect()
+    }
+}
+
+impl Default for BagRandomizer {
+    fn default() -> Self {
+        BagRandomizer::new(rand::random())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tetris_core::piece::Tetrominos::{I, J, L, O, S, T, Z};
+
+    fn take(randomizer: &mut dyn Randomizer, count: usize) -> Vec<Tetrominos> {
+        (0..count).map(|_| randomizer.advance()).collect()
+    }
+
+    // NOTE: if these fail, seeds of old games no longer reproduce the same pieces
+    #[test]
+    fn stream_sequence_is_pinned_for_a_seed() {
+        let mut stream = RandomStream::new(42);
+        assert_eq!(take(&mut stream, 14), vec![O, J, Z, J, O, L, O, J, I, J, L, O, S, T]);
+    }
+
+    #[test]
+    fn bag_sequence_is_pinned_for_a_seed() {
+        let mut bag = BagRandomizer::new(42);
+        assert_eq!(take(&mut bag, 14), vec![T, S, L, Z, I, O, J, O, J, Z, I, T, L, S]);
+    }
+
+    #[test]
+    fn preview_shows_the_next_pieces_for_any_count() {
+        let mut stream = RandomStream::new(42);
+        let mut bag = BagRandomizer::new(42);
+        let upcoming = (stream.preview(20), bag.preview(20));
+        assert_eq!(upcoming.0, take(&mut stream, 20));
+        assert_eq!(upcoming.1, take(&mut bag, 20));
+    }
+
+    #[test]
+    fn stream_never_repeats_a_piece() {
+        let mut stream = RandomStream::new(7);
+        let pieces = take(&mut stream, 1000);
+        assert!(pieces.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn every_bag_contains_each_piece_once() {
+        let mut bag = BagRandomizer::new(7);
+        for _ in 0..100 {
+            let pieces = take(&mut bag, 7);
+            for piece in &[I, J, L, O, S, T, Z] {
+                assert!(pieces.contains(piece));
+            }
+        }
+    }
+}
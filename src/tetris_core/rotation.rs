@@ -0,0 +1,217 @@
+use crate::constants::{WALL_KICKS_180, WALL_KICKS_I, WALL_KICKS_JLSTZ};
+use crate::tetris_core::board::Board;
+use crate::tetris_core::piece::{ActivePiece, Tetrominos};
+
+/// Rotates the piece around its rotation center and tries all wall kicks until one of them
+/// results in a free position, the rotation state is updated accordingly
+/// O pieces never rotate
+/// Returns whether the piece was rotated
+pub fn rotate(piece: &mut ActivePiece, board: &Board, dir_clockwise: bool) -> bool {
+    let wall_kicks = match get_wall_kick_data(&piece.piece, piece.rotation, dir_clockwise) {
+        Some(wall_kicks) => wall_kicks,
+        None => return false,
+    };
+
+    let (cx, cy) = piece.center;
+    let rotated_coords: Vec<(i32, i32)> = piece.blocks
+        .iter()
+        .map(|(x, y)| get_rotated(*x, *y, cx, cy, dir_clockwise))
+        .collect();
+
+    let rotation = next_rotation(piece.rotation, dir_clockwise);
+    apply_first_kick(piece, board, rotated_coords, wall_kicks, rotation)
+}
+
+/// Rotates the piece by 180 deg around its rotation center, using the 180 deg wall kicks
+/// O pieces never rotate
+/// Returns whether the piece was rotated
+pub fn rotate_180(piece: &mut ActivePiece, board: &Board) -> bool {
+    if piece.piece == Tetrominos::O {
+        return false;
+    }
+
+    // Rotating by 180 deg mirrors every block at the rotation center
+    let (cx, cy) = piece.center;
+    let rotated_coords: Vec<(i32, i32)> = piece.blocks
+        .iter()
+        .map(|(x, y)| (2 * cx - x, 2 * cy - y))
+        .collect();
+
+    let wall_kicks = &WALL_KICKS_180[piece.rotation as usize];
+    let rotation = (piece.rotation + 2) % 4;
+    apply_first_kick(piece, board, rotated_coords, wall_kicks, rotation)
+}
+
+/// Moves the rotated blocks by the first wall kick which results in a free position and updates
+/// the piece to the new rotation state
+/// Returns whether any of the kicks could be applied
+fn apply_first_kick(
+    piece: &mut ActivePiece,
+    board: &Board,
+    rotated_coords: Vec<(i32, i32)>,
+    wall_kicks: &[(i32, i32)],
+    rotation: i32,
+) -> bool {
+    // NOTE: the kicks are given in blocks, one block being two units in coordinate space
+    let possible_kick = wall_kicks.iter().find(|(dx, dy)| {
+        rotated_coords
+            .iter()
+            .all(|(x, y)| board.is_free(x + dx * 2, y + dy * 2))
+    });
+
+    match possible_kick {
+        Some((dx, dy)) => {
+            piece.blocks = rotated_coords
+                .iter()
+                .map(|(x, y)| (x + dx * 2, y + dy * 2))
+                .collect();
+            piece.center = (piece.center.0 + dx * 2, piece.center.1 + dy * 2);
+            piece.rotation = rotation;
+            true
+        },
+        None => false,
+    }
+}
+
+/// Returns the kicks to try when rotating the piece out of the given rotation state
+/// Returns None for the O piece, which does not rotate
+fn get_wall_kick_data(piece: &Tetrominos, current_rotation: i32, dir_clockwise: bool) -> Option<&'static [(i32, i32); 5]> {
+    let table = match piece {
+        Tetrominos::O => return None,
+        Tetrominos::I => &WALL_KICKS_I,
+        _ => &WALL_KICKS_JLSTZ,
+    };
+    Some(&table[kick_index(current_rotation, dir_clockwise)])
+}
+
+/// Returns the row of the kick tables for a rotation out of the given rotation state
+/// The rows are ordered by pairs, 2 * r being r >> r + 1 and 2 * r + 1 being its reverse
+pub fn kick_index(current_rotation: i32, dir_clockwise: bool) -> usize {
+    if dir_clockwise {
+        (2 * current_rotation) as usize
+    } else {
+        (2 * next_rotation(current_rotation, false) + 1) as usize
+    }
+}
+
+/// Returns the rotation state after rotating once in the given direction
+/// The states are 0 (spawn), 1 (right), 2 (180 deg) and 3 (left)
+pub fn next_rotation(current_rotation: i32, dir_clockwise: bool) -> i32 {
+    let step = if dir_clockwise { 1 } else { 3 };
+    (current_rotation + step) % 4
+}
+
+
+/// Rotates a point p around the rotation point r, either clockwise or counterclockwise by 90 deg
+pub fn get_rotated(px: i32, py: i32, rx: i32, ry:i32, dir_clockwise: bool) -> (i32, i32) {
+    let rel_x = px - rx;
+    let rel_y = py - ry;
+
+    let coefficient = if dir_clockwise { 1 } else { -1 };
+
+    let rel_rot_x = rel_y * coefficient;
+    let rel_rot_y = -1 * rel_x * coefficient;
+
+    let rot_x = rel_rot_x + rx;
+    let rot_y = rel_rot_y + ry;
+
+
+    (rot_x, rot_y)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tetris_core::piece::get_layout;
+
+    /// Creates a piece in its spawn orientation with its spawning block at the given cell
+    fn piece_at(piece: Tetrominos, column: i32, row: i32) -> ActivePiece {
+        ActivePiece {
+            piece,
+            blocks: get_layout(&piece).iter().map(|(x, y)| ((column + x) * 2, (row + y) * 2)).collect(),
+            center: (column * 2, row * 2),
+            rotation: 0,
+        }
+    }
+
+    fn cells(piece: &ActivePiece) -> Vec<(i32, i32)> {
+        let mut cells: Vec<(i32, i32)> = piece.blocks.iter().map(|(x, y)| (x / 2, y / 2)).collect();
+        cells.sort();
+        cells
+    }
+
+    #[test]
+    fn jlstz_kicks_between_spawn_and_right() {
+        let zero_to_right = get_wall_kick_data(&Tetrominos::T, 0, true).unwrap();
+        assert_eq!(zero_to_right, &[(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)]);
+        let right_to_zero = get_wall_kick_data(&Tetrominos::T, 1, false).unwrap();
+        assert_eq!(right_to_zero, &[(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn i_kicks_from_spawn_to_right_and_from_180_to_left() {
+        let zero_to_right = get_wall_kick_data(&Tetrominos::I, 0, true).unwrap();
+        assert_eq!(zero_to_right, &[(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)]);
+        let half_to_left = get_wall_kick_data(&Tetrominos::I, 2, true).unwrap();
+        assert_eq!(half_to_left, &[(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)]);
+    }
+
+    #[test]
+    fn o_piece_has_no_kicks_and_does_not_rotate() {
+        for rotation in 0..4 {
+            assert!(get_wall_kick_data(&Tetrominos::O, rotation, true).is_none());
+            assert!(get_wall_kick_data(&Tetrominos::O, rotation, false).is_none());
+        }
+        let board = Board::default();
+        let mut piece = piece_at(Tetrominos::O, 4, 4);
+        let before = piece.blocks.clone();
+        assert!(!rotate(&mut piece, &board, true));
+        assert!(!rotate_180(&mut piece, &board));
+        assert_eq!(piece.blocks, before);
+        assert_eq!(piece.rotation, 0);
+    }
+
+    #[test]
+    fn rotation_states_cycle_in_both_directions() {
+        assert_eq!(next_rotation(0, true), 1);
+        assert_eq!(next_rotation(3, true), 0);
+        assert_eq!(next_rotation(0, false), 3);
+        assert_eq!(kick_index(0, true), 0);
+        assert_eq!(kick_index(1, false), 1);
+        assert_eq!(kick_index(0, false), 7);
+        assert_eq!(kick_index(3, true), 6);
+    }
+
+    #[test]
+    fn t_spin_triple_uses_the_last_kick() {
+        // Three rows with a gap in column 3 and a roof over the middle row:
+        //
+        //   ..##......
+        //   ..#.......
+        //   ###.######
+        //   ###..#####
+        //   ###.######
+        let mut board = Board::default();
+        for row in 0..3 {
+            for column in 0..10 {
+                let gap = column == 3 || (row == 1 && column == 4);
+                board.set_occupied(column * 2, row * 2, !gap);
+            }
+        }
+        for (column, row) in &[(2, 3), (2, 4), (3, 4)] {
+            board.set_occupied(column * 2, row * 2, true);
+        }
+
+        // The T rests above the slot and is rotated into it, every kick but the last is blocked
+        let mut piece = piece_at(Tetrominos::T, 4, 3);
+        assert!(piece.fits(&board));
+        assert!(rotate(&mut piece, &board, true));
+        assert_eq!(piece.rotation, 1);
+        assert_eq!(piece.center, (6, 2));
+        assert_eq!(cells(&piece), vec![(3, 0), (3, 1), (3, 2), (4, 1)]);
+
+        assert_eq!(board.lock(&piece), None);
+        assert_eq!(board.full_rows(), vec![0, 2, 4]);
+    }
+}
@@ -0,0 +1,109 @@
+/// Keeps track of the player's score
+/// back_to_back: whether the last clear was a tetris, in which case the next tetris earns a bonus
+pub struct Score {
+    pub points: u64,
+    pub back_to_back: bool,
+}
+
+impl Score {
+    /// Awards the points for clearing the given number of lines at the given level and returns them
+    /// Every clear which is not a tetris ends a back-to-back chain
+    pub fn add_clear(&mut self, lines: usize, level: u32) -> u64 {
+        if lines == 0 {
+            return 0;
+        }
+        let mut points = clear_points(lines) * level as u64;
+        let is_tetris = lines == 4;
+        if is_tetris && self.back_to_back {
+            // A back-to-back tetris is worth one and a half times as much
+            points = points * 3 / 2;
+        }
+        self.back_to_back = is_tetris;
+        self.points += points;
+        points
+    }
+
+    /// Awards one point for every cell the piece was moved down by soft drop
+    pub fn add_soft_drop(&mut self, cells: u32) {
+        self.points += cells as u64;
+    }
+
+    /// Awards two points for every cell the piece was moved down by hard drop
+    pub fn add_hard_drop(&mut self, cells: u32) {
+        self.points += cells as u64 * 2;
+    }
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Score { points: 0, back_to_back: false }
+    }
+}
+
+/// Returns the points for clearing the given number of lines at level 1
+fn clear_points(lines: usize) -> u64 {
+    match lines {
+        1 => 100,
+        2 => 300,
+        3 => 500,
+        _ => 800,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clears_are_worth_the_guideline_points() {
+        for (lines, points) in &[(1, 100), (2, 300), (3, 500), (4, 800)] {
+            let mut score = Score::default();
+            assert_eq!(score.add_clear(*lines, 1), *points);
+            assert_eq!(score.points, *points);
+        }
+    }
+
+    #[test]
+    fn no_lines_are_worth_nothing() {
+        let mut score = Score::default();
+        assert_eq!(score.add_clear(0, 5), 0);
+        assert_eq!(score.points, 0);
+    }
+
+    #[test]
+    fn points_are_multiplied_by_the_level() {
+        let mut score = Score::default();
+        assert_eq!(score.add_clear(1, 3), 300);
+        assert_eq!(score.add_clear(3, 10), 5000);
+        assert_eq!(score.points, 5300);
+    }
+
+    #[test]
+    fn back_to_back_tetris_earns_half_again() {
+        let mut score = Score::default();
+        assert_eq!(score.add_clear(4, 1), 800);
+        assert!(score.back_to_back);
+        assert_eq!(score.add_clear(4, 1), 1200);
+        assert_eq!(score.add_clear(4, 2), 2400);
+        assert_eq!(score.points, 4400);
+    }
+
+    #[test]
+    fn other_clears_end_the_back_to_back_chain() {
+        let mut score = Score::default();
+        score.add_clear(4, 1);
+        assert_eq!(score.add_clear(2, 1), 300);
+        assert!(!score.back_to_back);
+        assert_eq!(score.add_clear(4, 1), 800);
+    }
+
+    #[test]
+    fn drops_earn_points_per_cell() {
+        let mut score = Score::default();
+        score.add_soft_drop(5);
+        assert_eq!(score.points, 5);
+        score.add_hard_drop(10);
+        assert_eq!(score.points, 25);
+    }
+}
@@ -0,0 +1,229 @@
+use std::time::Duration;
+
+use crate::constants::{ARENA_HEIGHT, LOCK_DELAY, LOCK_RESET_LIMIT, SOFT_DROP_FACTOR};
+
+/// Keeps track of the time since the last time that gravity was applied
+/// Implements methods for reducing the time between gravity applications
+///
+/// Is both set and reset when applying gravity, soft drop is toggled while the descend input is held
+/// The threshold is set from the gravity curve whenever the level changes
+pub struct GravityTimer {
+    pub timer: Duration,
+    pub threshold: Duration,
+    pub soft_drop: bool,
+    pub soft_drop_factor: u32,
+}
+
+impl GravityTimer {
+    pub fn add_time(&mut self, time: Duration) {
+        self.timer += time;
+    }
+
+    /// Returns the number of rows the piece has to fall by and consumes the corresponding time
+    /// Once the threshold is shorter than a frame the piece falls by several rows at once
+    pub fn take_rows(&mut self) -> u32 {
+        // No piece can fall further than the height of the arena
+        let max_rows = (ARENA_HEIGHT / 2) as u32;
+        let threshold = self.current_threshold();
+        let mut rows = 0;
+        while self.timer >= threshold && rows < max_rows {
+            self.timer -= threshold;
+            rows += 1;
+        }
+        if rows == max_rows {
+            self.reset();
+        }
+        rows
+    }
+
+    pub fn reset(&mut self) {
+        self.timer = Duration::new(0, 0);
+    }
+
+    pub fn set_threshold(&mut self, time: Duration) {
+        self.threshold = time;
+    }
+
+    pub fn set_soft_drop(&mut self, active: bool) {
+        self.soft_drop = active;
+    }
+
+    /// Returns the time between gravity applications, which is divided by the soft drop factor
+    /// while soft drop is active
+    pub fn current_threshold(&self) -> Duration {
+        if self.soft_drop {
+            self.threshold / self.soft_drop_factor
+        } else {
+            self.threshold
+        }
+    }
+}
+
+impl Default for GravityTimer {
+    fn default() -> Self {
+        Self {
+            timer: Duration::from_millis(0),
+            threshold: Duration::from_millis(500),
+            soft_drop: false,
+            soft_drop_factor: SOFT_DROP_FACTOR,
+        }
+    }
+}
+
+
+/// Keeps track of how long the falling piece has been resting on the stack
+/// Implements methods for restarting the delay when the piece is moved, up to reset_limit times
+///
+/// Is started and checked when applying gravity, reset whenever a piece is spawned
+/// The delay and the number of resets only start over once the piece falls below the lowest row
+/// it has reached, so stepping off a ledge and back onto it does not stall the piece
+pub struct LockTimer {
+    pub timer: Duration,
+    pub delay: Duration,
+    pub active: bool,
+    pub resets: u32,
+    pub reset_limit: u32,
+    // The lowest y coordinate any block of the piece has reached
+    lowest_row: Option<i32>,
+}
+
+impl LockTimer {
+    pub fn add_time(&mut self, time: Duration) {
+        if self.active {
+            self.timer += time;
+        }
+    }
+
+    pub fn should_lock(&self) -> bool {
+        self.active && self.timer >= self.delay
+    }
+
+    /// Starts the delay if it is not running already
+    pub fn start(&mut self) {
+        self.active = true;
+    }
+
+    /// Pauses the delay once the piece is no longer resting on the stack
+    /// The elapsed time and the number of resets are kept, so that it is not possible to stall
+    /// indefinitely
+    pub fn stop(&mut self) {
+        self.active = false;
+    }
+
+    /// Should be called with the lowest y coordinate of the piece whenever it may have moved down
+    /// Starts the delay and the resets over if the piece has reached a new lowest row
+    pub fn set_row(&mut self, row: i32) {
+        let lower = match self.lowest_row {
+            Some(lowest) => row < lowest,
+            None => true,
+        };
+        if lower {
+            self.lowest_row = Some(row);
+            self.timer = Duration::new(0, 0);
+            self.resets = 0;
+        }
+    }
+
+    /// Restarts the delay after the piece was moved or rotated, as long as the limit of resets
+    /// has not been reached yet
+    pub fn on_move(&mut self) {
+        if self.active && self.resets < self.reset_limit {
+            self.timer = Duration::new(0, 0);
+            self.resets += 1;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.stop();
+        self.timer = Duration::new(0, 0);
+        self.resets = 0;
+        self.lowest_row = None;
+    }
+}
+
+impl Default for LockTimer {
+    fn default() -> Self {
+        Self {
+            timer: Duration::from_millis(0),
+            delay: LOCK_DELAY,
+            active: false,
+            resets: 0,
+            reset_limit: LOCK_RESET_LIMIT,
+            lowest_row: None,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn piece_locks_once_the_delay_has_passed() {
+        let mut timer = LockTimer::default();
+        timer.set_row(0);
+        timer.start();
+        timer.add_time(LOCK_DELAY - ms(1));
+        assert!(!timer.should_lock());
+        timer.add_time(ms(1));
+        assert!(timer.should_lock());
+    }
+
+    #[test]
+    fn moves_reset_the_delay_up_to_the_limit() {
+        let mut timer = LockTimer::default();
+        timer.set_row(0);
+        timer.start();
+        for _ in 0..LOCK_RESET_LIMIT {
+            timer.add_time(LOCK_DELAY - ms(1));
+            timer.on_move();
+            assert!(!timer.should_lock());
+        }
+        timer.add_time(LOCK_DELAY - ms(1));
+        timer.on_move();
+        timer.add_time(ms(1));
+        assert!(timer.should_lock());
+    }
+
+    #[test]
+    fn leaving_the_ground_does_not_restart_the_delay() {
+        let mut timer = LockTimer::default();
+        timer.set_row(10);
+        timer.start();
+        timer.add_time(ms(400));
+
+        // Stepping off a ledge and back onto it at the same height
+        timer.stop();
+        timer.add_time(ms(1000));
+        timer.set_row(10);
+        timer.start();
+        assert!(!timer.should_lock());
+        timer.add_time(ms(100));
+        assert!(timer.should_lock());
+    }
+
+    #[test]
+    fn reaching_a_lower_row_restarts_the_delay_and_the_resets() {
+        let mut timer = LockTimer::default();
+        timer.set_row(10);
+        timer.start();
+        for _ in 0..LOCK_RESET_LIMIT {
+            timer.on_move();
+        }
+        timer.add_time(ms(400));
+        timer.stop();
+
+        timer.set_row(8);
+        timer.start();
+        assert_eq!(timer.resets, 0);
+        timer.add_time(ms(400));
+        timer.on_move();
+        timer.add_time(ms(400));
+        assert!(!timer.should_lock());
+    }
+}